@@ -0,0 +1,42 @@
+//! Embed the version of the build, so generated code files can tell
+//! which build generated them. The version is the package version and
+//! the output of `git describe` as build metadata if the crate is built
+//! from a Git repository, like `0.0.0+v0.1-3-gabc1234-dirty`. Build
+//! metadata is only information and does not make generated code stale.
+
+use std::path::Path;
+use std::process::Command;
+
+fn main() {
+    let package_version = std::env::var("CARGO_PKG_VERSION").unwrap();
+    let build_version = match git_describe() {
+        Some(description) => format!("{}+{}", package_version, description),
+        None => package_version,
+    };
+    println!("cargo:rustc-env=ICEGEN_BUILD_VERSION={}", build_version);
+
+    // Build again when a commit is made or checked out, or when files
+    // are changed which makes the working tree dirty
+    for path in [
+        ".git/HEAD",
+        ".git/refs",
+        ".git/packed-refs",
+        ".git/index",
+        "src",
+    ] {
+        if Path::new(path).exists() {
+            println!("cargo:rerun-if-changed={}", path);
+        }
+    }
+}
+
+fn git_describe() -> Option<String> {
+    let output = Command::new("git")
+        .args(["describe", "--always", "--tags", "--dirty"])
+        .output()
+        .ok()
+        .filter(|output| output.status.success())?;
+    let description = String::from_utf8(output.stdout).ok()?;
+    let description = description.trim();
+    (!description.is_empty()).then(|| description.to_string())
+}
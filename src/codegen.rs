@@ -1,15 +1,22 @@
 //! Generate Dart data classes
 
+use std::collections::{HashMap, HashSet};
+
 use anyhow::Result;
 
 use crate::{
+    diagnosticable::DiagnosticableLibraries,
+    error::{LocatedError, Location},
     file_finder::DartFile,
-    parser::{ClassDefinition, ParsedFile, TopLevelItems},
+    parser::{ParsedFile, TopLevelItems},
 };
 
-use self::data_class::ValidatedClass;
+use self::data_class::impl_class::DiagnosticsPropertyClass;
+pub(crate) use self::data_class::ValidatedClass;
 
 mod data_class;
+pub mod header;
+pub mod part_directive;
 mod part_of;
 mod utils;
 
@@ -45,55 +52,158 @@ impl StringEditor {
     }
 }
 
+/// Names from `package:flutter/foundation.dart` which generated
+/// Diagnosticable support code uses.
+const DIAGNOSTICABLE_NAMES: &[&str] = &[
+    "DiagnosticableTreeMixin",
+    "DiagnosticLevel",
+    "DiagnosticPropertiesBuilder",
+    "DiagnosticsProperty",
+];
+
 struct ValidatedFile {
     pub flutter_foundation_import_exists: bool,
+    /// Property classes which the imports make visible without prefix
+    pub visible_property_classes: HashSet<DiagnosticsPropertyClass>,
+    pub enum_names: HashSet<String>,
+    pub classes: Vec<ValidatedClass>,
 }
 
 impl ValidatedFile {
-    pub fn validate(parsed_file: &ParsedFile) -> Result<Self> {
-        let mut flutter_foundation_import_exists = false;
+    pub fn validate(file: &DartFile, libraries: &DiagnosticableLibraries) -> Result<Self> {
+        let parsed_file = &file.parsed_file;
+        let mut imports = vec![];
+        let mut classes = vec![];
+        let mut enum_names = HashSet::new();
 
         for item in &parsed_file.items {
-            if let TopLevelItems::Import(import) = item {
-                if import.is_flutter_foundation_import() {
-                    flutter_foundation_import_exists = true;
+            match item {
+                TopLevelItems::Import(import) => imports.push(import),
+                TopLevelItems::Class(class) => {
+                    if class.contains_freezed_annotation() {
+                        // Errors of the class are located at its name
+                        // if they have no location
+                        let (line, column) = class.name_position.line_and_column(&file.contents);
+                        let validated = ValidatedClass::validate(class, file)
+                            .map_err(|e| LocatedError::locate(e, Location { line, column }))?;
+                        classes.push(validated);
+                    }
+                }
+                TopLevelItems::Enum(enum_definition) => {
+                    enum_names.insert(enum_definition.name.to_string());
                 }
+                TopLevelItems::Library(_)
+                | TopLevelItems::Export(_)
+                | TopLevelItems::Part(_)
+                | TopLevelItems::PartOf(_) => (),
             }
         }
 
+        Self::validate_generated_names(parsed_file, &classes)?;
+
+        // Multiple imports of the same library are combined to
+        // one library namespace.
+        let flutter_foundation_import_exists = DIAGNOSTICABLE_NAMES.iter().all(|name| {
+            imports
+                .iter()
+                .any(|import| libraries.import_makes_visible(&file.path, import, name))
+        });
+
+        let visible_property_classes = DiagnosticsPropertyClass::ALL
+            .iter()
+            .copied()
+            .filter(|property_class| {
+                imports.iter().any(|import| {
+                    libraries.import_makes_visible(&file.path, import, property_class.name())
+                })
+            })
+            .collect();
+
         Ok(ValidatedFile {
             flutter_foundation_import_exists,
+            visible_property_classes,
+            enum_names,
+            classes,
         })
     }
-}
 
-pub fn generate_data_class_file(file: &DartFile) -> Result<String> {
-    let validated = ValidatedFile::validate(&file.parsed_file)?;
-
-    let mut nullable_named_parameter_exists = false;
-    let mut class_specific_code = StringEditor::new(String::new());
+    /// Check that generated top level names do not clash with each other
+    /// or with classes declared in the file.
+    fn validate_generated_names(
+        parsed_file: &ParsedFile,
+        classes: &[ValidatedClass],
+    ) -> Result<()> {
+        let mut declared_names = HashMap::<String, String>::new();
 
-    for item in &file.parsed_file.items {
-        if let TopLevelItems::Class(class) = item {
-            if !class.contains_freezed_annotation() {
-                continue;
+        for item in &parsed_file.items {
+            match item {
+                TopLevelItems::Class(class) => {
+                    declared_names.insert(
+                        class.name.to_string(),
+                        format!("class {} declared in the same file", class.name),
+                    );
+                }
+                TopLevelItems::Enum(enum_definition) => {
+                    declared_names.insert(
+                        enum_definition.name.to_string(),
+                        format!("enum {} declared in the same file", enum_definition.name),
+                    );
+                }
+                TopLevelItems::Library(_)
+                | TopLevelItems::Import(_)
+                | TopLevelItems::Export(_)
+                | TopLevelItems::Part(_)
+                | TopLevelItems::PartOf(_) => (),
             }
+        }
 
-            generate_data_class(
-                &validated,
-                class,
-                &mut class_specific_code,
-                &mut nullable_named_parameter_exists,
-            )?;
+        for class in classes {
+            for name in class.generated_top_level_names() {
+                if let Some(existing) = declared_names.get(&name) {
+                    return Err(LocatedError {
+                        location: class.name_location(),
+                        message: format!(
+                            "Generated name {} for class {} clashes with {}",
+                            name,
+                            class.name(),
+                            existing
+                        ),
+                    }
+                    .into());
+                }
+                declared_names.insert(name, format!("code generated for class {}", class.name()));
+            }
         }
+
+        Ok(())
     }
+}
 
-    let mut editor = StringEditor::new(GENERATED_FILE_HEADER.to_string());
+pub fn generate_data_class_file(
+    file: &DartFile,
+    libraries: &DiagnosticableLibraries,
+) -> Result<String> {
+    let validated = ValidatedFile::validate(file, libraries)?;
+
+    let mut nullable_parameter_exists = false;
+    let mut class_specific_code = StringEditor::new(String::new());
+
+    for class in &validated.classes {
+        generate_data_class(
+            &validated,
+            class,
+            &mut class_specific_code,
+            &mut nullable_parameter_exists,
+        )
+        .map_err(|e| LocatedError::locate(e, class.name_location()))?;
+    }
+
+    let mut editor = StringEditor::new(header::generated_file_header(&file.contents));
 
     editor.add_paragraph(part_of::generate_part_of_statement(file)?);
     editor.add_paragraph(GENERATOR_INFO_TEXT);
 
-    if nullable_named_parameter_exists {
+    if nullable_parameter_exists {
         editor.add_paragraph(data_class::impl_class::generate_detect_default_class_and_constant());
     }
 
@@ -104,22 +214,100 @@ pub fn generate_data_class_file(file: &DartFile) -> Result<String> {
 
 fn generate_data_class(
     file: &ValidatedFile,
-    class: &ClassDefinition,
+    validated: &ValidatedClass,
     editor: &mut StringEditor,
-    nullable_named_paramter_exists: &mut bool,
+    nullable_parameter_exists: &mut bool,
 ) -> Result<()> {
-    let validated = ValidatedClass::validate(class)?;
-
-    editor.add_paragraph(data_class::mixin::generate_mixin(&validated)?);
+    editor.add_paragraph(data_class::mixin::generate_mixin(validated)?);
     editor.add_paragraph(data_class::abstract_class::generate_abstract_class(
-        &validated,
+        validated,
     )?);
     editor.add_paragraph(data_class::impl_class::generate_impl_class(
-        file, &validated,
+        file, validated,
     )?);
 
-    *nullable_named_paramter_exists =
-        *nullable_named_paramter_exists || validated.nullable_named_parameter_exists();
+    *nullable_parameter_exists = *nullable_parameter_exists
+        || (validated.options().copy_with && validated.nullable_parameter_exists());
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use super::*;
+
+    fn generate(source: &str) -> Result<String> {
+        let file = DartFile::parse(Path::new("a.dart"), source).unwrap();
+        generate_data_class_file(&file, &DiagnosticableLibraries::new(&[], false))
+    }
+
+    #[test]
+    fn parameter_metadata_is_copied_onto_members() {
+        let generated = generate(
+            "@freezed
+class A with _$A {
+  factory A({
+    /// Name of the user
+    @Deprecated('Use fullName') @JsonKey(name: 'user_name') required String name,
+  }) = _A;
+}",
+        )
+        .unwrap();
+
+        // Getter of the mixin
+        assert!(generated.contains(
+            "  /// Name of the user
+  @Deprecated('Use fullName')
+  @JsonKey(name: 'user_name')
+  String get name => throw _privateConstructorErrorA;"
+        ));
+        // Field of the implementation class
+        assert!(generated.contains(
+            "  /// Name of the user
+  @override
+  @Deprecated('Use fullName')
+  @JsonKey(name: 'user_name')
+  final String name;"
+        ));
+        // Parameters of copyWith in the mixin and the implementation class
+        assert!(generated
+            .contains("    @Deprecated('Use fullName') @JsonKey(name: 'user_name') String? name,"));
+        assert!(generated
+            .contains("    @Deprecated('Use fullName') @JsonKey(name: 'user_name') Object? name,"));
+    }
+
+    #[test]
+    fn generated_names_clash_with_declarations_in_same_file() {
+        let error = generate(
+            "@freezed class A with _$A { factory A({int a}) = _A; }
+class _A {}",
+        )
+        .unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Generated name _A for class A clashes with class _A declared in the same file"
+        );
+
+        let error = generate(
+            "enum _$AImpl { a }
+@freezed class A with _$A { factory A({int a}) = _A; }",
+        )
+        .unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Generated name _$AImpl for class A clashes with enum _$AImpl declared in the same file"
+        );
+
+        let error = generate(
+            "@freezed class A with _$A { factory A({int a}) = _B; }
+@freezed class B with _$B { factory B({int b}) = _B; }",
+        )
+        .unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Generated name _B for class B clashes with code generated for class A"
+        );
+    }
+}
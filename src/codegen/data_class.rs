@@ -1,20 +1,70 @@
 pub mod abstract_class;
+pub mod default_value;
 pub mod impl_class;
 pub mod mixin;
+pub mod options;
+
+use std::collections::HashSet;
 
 use anyhow::{anyhow, Result};
 
+use self::{
+    default_value::default_value_error,
+    impl_class::{DiagnosticsPropertyClass, DEFAULT_DETECTOR_VARIABLE},
+    options::FreezedOptions,
+};
+
+use crate::error::{LocatedError, Location};
+use crate::file_finder::DartFile;
 use crate::parser::{
-    ClassDefinition, ClassItem, FactoryConstructor, Identifier, NamedParameter, PrivateConstructor,
+    Annotation, ClassDefinition, ClassItem, ClassMember, FactoryConstructor, Identifier, Parameter,
+    ParameterKind, PrivateConstructor,
 };
 
+/// Names of members which all generated data classes have
+const RESERVED_MEMBER_NAMES: &[&str] = &[
+    "copyWith",
+    "hashCode",
+    "noSuchMethod",
+    "runtimeType",
+    "toString",
+];
+
+/// Assertion from annotation like `@Assert('a.isNotEmpty', 'message')`
+pub struct Assertion {
+    /// Dart expression
+    pub expression: String,
+    /// Dart string literal
+    pub message: Option<String>,
+}
+
 pub struct ValidatedClass {
     name: Identifier,
+    /// Location of the class name in the Dart file
+    name_location: Location,
     private_constructor: Option<PrivateConstructor>,
     factory_constructor: FactoryConstructor,
+    options: FreezedOptions,
+    assertions: Vec<Assertion>,
+    /// Types from `@With<T>()` annotations
+    additional_mixins: Vec<String>,
+    /// Types from `@Implements<T>()` annotations
+    additional_interfaces: Vec<String>,
 }
 
 impl ValidatedClass {
+    pub fn name(&self) -> &Identifier {
+        &self.name
+    }
+
+    pub fn name_location(&self) -> Location {
+        self.name_location
+    }
+
+    pub fn options(&self) -> &FreezedOptions {
+        &self.options
+    }
+
     pub fn private_constructor_exists(&self) -> bool {
         self.private_constructor.is_some()
     }
@@ -26,26 +76,72 @@ impl ValidatedClass {
             .unwrap_or_default()
     }
 
-    pub fn factory_constructor_params(&self) -> &[NamedParameter] {
+    pub fn factory_constructor_params(&self) -> &[Parameter] {
         &self.factory_constructor.params
     }
 
+    /// Fields are final if `addImplicitFinal` is enabled or
+    /// the parameter has the final keyword.
+    pub fn field_is_final(&self, field: &Parameter) -> bool {
+        self.options.add_implicit_final || field.is_final
+    }
+
+    pub fn factory_constructor_assertions(&self) -> &[Assertion] {
+        &self.assertions
+    }
+
+    pub fn additional_mixins(&self) -> &[String] {
+        &self.additional_mixins
+    }
+
+    pub fn additional_interfaces(&self) -> &[String] {
+        &self.additional_interfaces
+    }
+
+    pub fn factory_constructor_doc_comment(&self) -> &[String] {
+        &self.factory_constructor.doc_comment
+    }
+
+    pub fn factory_constructor_deprecated_annotation(&self) -> Option<&Annotation> {
+        self.factory_constructor
+            .annotations
+            .iter()
+            .find(|annotation| annotation.is_deprecated_annotation())
+    }
+
     pub fn factory_constructor_is_const(&self) -> bool {
         self.factory_constructor.is_const
     }
 
-    pub fn nullable_named_parameter_exists(&self) -> bool {
+    /// Name of the generated abstract class, which is the redirect
+    /// target of the factory constructor.
+    pub fn abstract_class_name(&self) -> &Identifier {
+        &self.factory_constructor.redirect_target
+    }
+
+    /// Top level names which the generated code for this class declares.
+    pub fn generated_top_level_names(&self) -> Vec<String> {
+        vec![
+            format!("_${}", self.name),
+            format!("_${}Impl", self.name),
+            format!("_privateConstructorError{}", self.name),
+            self.abstract_class_name().to_string(),
+        ]
+    }
+
+    pub fn nullable_parameter_exists(&self) -> bool {
         self.factory_constructor
             .params
             .iter()
-            .any(|param| param.parameter_type.nullable)
+            .any(|param| param.parameter_type.is_nullable())
     }
 }
 
 impl ValidatedClass {
-    pub fn validate(class_info: &ClassDefinition) -> Result<ValidatedClass> {
+    pub fn validate(class_info: &ClassDefinition, file: &DartFile) -> Result<ValidatedClass> {
         let mut private_constructor: Option<PrivateConstructor> = None;
         let mut factory_constructor: Option<FactoryConstructor> = None;
+        let mut first_instance_member: Option<&ClassMember> = None;
 
         for item in &class_info.item_info.items {
             match item {
@@ -71,6 +167,11 @@ impl ValidatedClass {
                         private_constructor = Some(constructor.clone());
                     }
                 }
+                ClassItem::Member(member) => {
+                    if member.is_instance_member && first_instance_member.is_none() {
+                        first_instance_member = Some(member);
+                    }
+                }
             }
         }
 
@@ -79,22 +180,321 @@ impl ValidatedClass {
             class_info.name
         ))?;
 
+        if let Some(private_constructor) = &private_constructor {
+            if factory_constructor.is_const && !private_constructor.is_const {
+                return Err(anyhow!(
+                    "Factory constructor in class {} is const, but private constructor {}._() is not. Add the const keyword to the private constructor or remove it from the factory constructor.",
+                    class_info.name,
+                    class_info.name,
+                ));
+            }
+        }
+
+        Self::validate_mixin(class_info, file)?;
+
+        // Custom members are inherited by the generated class, which
+        // calls the private constructor
+        if let (None, Some(member)) = (&private_constructor, first_instance_member) {
+            let (line, column) = member.position.line_and_column(&file.contents);
+            return Err(LocatedError {
+                location: Location { line, column },
+                message: format!(
+                    "Class {} has custom members, but no private constructor. Add `{}{}._();` to the class body.",
+                    class_info.name,
+                    if factory_constructor.is_const { "const " } else { "" },
+                    class_info.name,
+                ),
+            }
+            .into());
+        }
+
+        Self::validate_default_values(&factory_constructor, class_info, file)?;
+
+        let options = Self::validate_options(class_info)?;
+        let assertions = Self::validate_assertions(&factory_constructor, class_info)?;
+        let mut additional_mixins = vec![];
+        let mut additional_interfaces = vec![];
+        for annotation in &factory_constructor.annotations {
+            if annotation.is_with_annotation() {
+                additional_mixins.push(Self::validate_type_annotation(annotation, class_info)?);
+            } else if annotation.is_implements_annotation() {
+                additional_interfaces.push(Self::validate_type_annotation(annotation, class_info)?);
+            }
+        }
+
+        if let Some(mutable_field) = factory_constructor
+            .params
+            .iter()
+            .find(|param| !options.add_implicit_final && !param.is_final)
+        {
+            if factory_constructor.is_const {
+                return Err(anyhow!(
+                    "Factory constructor in class {} is const, but field {} is mutable. Remove the const keyword or add the final keyword to all parameters.",
+                    class_info.name,
+                    mutable_field.name,
+                ));
+            }
+        }
+
+        let (line, column) = class_info.name_position.line_and_column(&file.contents);
         let validated = ValidatedClass {
             name: class_info.name.clone(),
+            name_location: Location { line, column },
             private_constructor,
             factory_constructor,
+            options,
+            assertions,
+            additional_mixins,
+            additional_interfaces,
         };
 
+        validated.validate_parameter_names()?;
+
         Ok(validated)
     }
 
+    /// The class must mix in the generated mixin `_$X`. Generic classes
+    /// are not supported, so the mixin does not have type arguments.
+    fn validate_mixin(class_info: &ClassDefinition, file: &DartFile) -> Result<()> {
+        let mixin_name = format!("_${}", class_info.name);
+        let mixin = class_info
+            .mixin_types
+            .iter()
+            .find(|mixin| mixin.base_name() == Some(mixin_name.as_str()));
+
+        let message = match mixin {
+            Some(mixin) if mixin.type_args().is_empty() => return Ok(()),
+            Some(mixin) => format!(
+                "Type arguments of mixin `{}` do not match the type parameters of class {}. Replace it with `{}`.",
+                mixin, class_info.name, mixin_name,
+            ),
+            None => format!(
+                "Class {} does not mix in the generated mixin {}. Add `with {}` after the class name.",
+                class_info.name, mixin_name, mixin_name,
+            ),
+        };
+
+        let (line, column) = class_info.name_position.line_and_column(&file.contents);
+        Err(LocatedError {
+            location: Location { line, column },
+            message,
+        }
+        .into())
+    }
+
+    /// Default values are used as values of `static const` variables
+    /// in the generated code, so they must be constant expressions
+    /// which can be assigned to the parameter type.
+    fn validate_default_values(
+        constructor: &FactoryConstructor,
+        class_info: &ClassDefinition,
+        file: &DartFile,
+    ) -> Result<()> {
+        for param in &constructor.params {
+            let Some(annotation) = param
+                .annotations
+                .iter()
+                .find(|annotation| annotation.is_default_annotation())
+            else {
+                continue;
+            };
+
+            if let Some(reason) = default_value_error(param, &annotation.parameters) {
+                let (line, column) = annotation.position.line_and_column(&file.contents);
+                return Err(LocatedError {
+                    location: Location { line, column },
+                    message: format!(
+                        "Invalid @Default value `{}` for parameter {} in class {}: {}",
+                        annotation.parameters.trim(),
+                        param.name,
+                        class_info.name,
+                        reason,
+                    ),
+                }
+                .into());
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Check that parameter names are unique and do not clash with
+    /// members or identifiers of the generated code.
+    fn validate_parameter_names(&self) -> Result<()> {
+        let mut generated_names = self.generated_top_level_names();
+        generated_names.push(DEFAULT_DETECTOR_VARIABLE.to_string());
+        for param in self.factory_constructor_params() {
+            if param.default_annotation().is_some() {
+                generated_names.push(format!("_{}DefaultValue", param.name));
+            }
+        }
+
+        let mut names = HashSet::new();
+
+        for param in self.factory_constructor_params() {
+            let name = param.name.as_str();
+
+            if !names.insert(name) {
+                return Err(anyhow!(
+                    "Parameter {} is defined multiple times in factory constructor of class {}",
+                    name,
+                    self.name,
+                ));
+            }
+
+            if RESERVED_MEMBER_NAMES.contains(&name) {
+                return Err(anyhow!(
+                    "Parameter {} in class {} clashes with member {} of the generated class. Rename the parameter.",
+                    name,
+                    self.name,
+                    name,
+                ));
+            }
+
+            if generated_names.iter().any(|generated| generated == name) {
+                return Err(anyhow!(
+                    "Parameter {} in class {} clashes with identifier {} of the generated code. Rename the parameter.",
+                    name,
+                    self.name,
+                    name,
+                ));
+            }
+        }
+
+        Ok(())
+    }
+
+    fn validate_options(class_info: &ClassDefinition) -> Result<FreezedOptions> {
+        let mut annotations = class_info.annotations.iter().filter(|annotation| {
+            annotation.is_freezed_annotation() || annotation.is_unfreezed_annotation()
+        });
+
+        let Some(annotation) = annotations.next() else {
+            return Ok(FreezedOptions::default());
+        };
+
+        if annotations.next().is_some() {
+            return Err(anyhow!(
+                "Multiple @freezed, @Freezed or @unfreezed annotations found for class {}",
+                class_info.name
+            ));
+        }
+
+        if annotation.is_unfreezed_annotation() {
+            Ok(FreezedOptions::unfreezed())
+        } else {
+            FreezedOptions::from_annotation(annotation, &class_info.name)
+        }
+    }
+
+    /// Get type from annotation like `@With<T>()` or `@With.fromString('T')`
+    fn validate_type_annotation(
+        annotation: &Annotation,
+        class_info: &ClassDefinition,
+    ) -> Result<String> {
+        let invalid_annotation = || {
+            anyhow!(
+                "Invalid @{} annotation for factory constructor in class {}. Use @{}<Type>() or @{}.fromString('Type').",
+                annotation.name,
+                class_info.name,
+                annotation.name,
+                annotation.name,
+            )
+        };
+
+        let arguments = annotation.arguments().ok_or_else(invalid_annotation)?;
+
+        match annotation.named_constructor.as_ref().map(|n| n.as_str()) {
+            None => match (annotation.type_args.as_slice(), arguments.is_empty()) {
+                ([data_type], true) => Ok(data_type.to_string()),
+                _ => Err(invalid_annotation()),
+            },
+            Some("fromString") => match (annotation.type_args.is_empty(), arguments.as_slice()) {
+                (true, [argument]) if argument.name.is_none() => argument
+                    .string_value()
+                    .filter(|value| !value.trim().is_empty())
+                    .map(|value| value.trim().to_string())
+                    .ok_or_else(invalid_annotation),
+                _ => Err(invalid_annotation()),
+            },
+            Some(_) => Err(invalid_annotation()),
+        }
+    }
+
+    fn validate_assertions(
+        constructor: &FactoryConstructor,
+        class_info: &ClassDefinition,
+    ) -> Result<Vec<Assertion>> {
+        let mut assertions = vec![];
+
+        for annotation in &constructor.annotations {
+            if !annotation.is_assert_annotation() {
+                continue;
+            }
+
+            let invalid_assert = || {
+                anyhow!(
+                    "Invalid @Assert({}) annotation for factory constructor in class {}. Use @Assert('expression') or @Assert('expression', 'message').",
+                    annotation.parameters,
+                    class_info.name
+                )
+            };
+
+            let arguments = annotation.arguments().ok_or_else(invalid_assert)?;
+
+            let (expression, message) = match arguments.as_slice() {
+                [expression] => (expression, None),
+                [expression, message] => (expression, Some(message)),
+                _ => return Err(invalid_assert()),
+            };
+
+            if expression.name.is_some() || message.is_some_and(|m| m.name.is_some()) {
+                return Err(invalid_assert());
+            }
+
+            let expression = expression.string_value().ok_or_else(invalid_assert)?;
+
+            if expression.trim().is_empty() {
+                return Err(invalid_assert());
+            }
+
+            let message = match message {
+                Some(message) => {
+                    message.string_value().ok_or_else(invalid_assert)?;
+                    Some(message.value.clone())
+                }
+                None => None,
+            };
+
+            assertions.push(Assertion {
+                expression,
+                message,
+            });
+        }
+
+        Ok(assertions)
+    }
+
     fn validate_factory_constructor(
         constructor: &FactoryConstructor,
         class_info: &ClassDefinition,
     ) -> Result<()> {
         if constructor.params.is_empty() {
             return Err(anyhow!(
-                "Factory constructor in class {} has no named parameters",
+                "Factory constructor in class {} has no parameters",
+                class_info.name
+            ));
+        }
+
+        let redirect_target = constructor.redirect_target.as_str();
+        if redirect_target == class_info.name.as_str()
+            || redirect_target == format!("_${}", class_info.name)
+            || redirect_target == format!("_${}Impl", class_info.name)
+        {
+            return Err(anyhow!(
+                "Factory constructor in class {} redirects to {}, which is reserved. Use _{} instead.",
+                class_info.name,
+                redirect_target,
                 class_info.name
             ));
         }
@@ -114,8 +514,227 @@ impl ValidatedClass {
                 }
                 default_annotation_found = true;
             }
+
+            Self::validate_diagnostics_property_class_annotations(param, class_info)?;
+
+            if default_annotation_found && param.kind == ParameterKind::Positional {
+                return Err(anyhow!(
+                    "@Default annotation found for required positional parameter {} in class {}",
+                    param.name,
+                    class_info.name
+                ));
+            }
+
+            if param.required && param.kind != ParameterKind::Named {
+                return Err(anyhow!(
+                    "Positional parameter {} in class {} has required keyword",
+                    param.name,
+                    class_info.name
+                ));
+            }
+        }
+
+        Ok(())
+    }
+
+    fn validate_diagnostics_property_class_annotations(
+        param: &Parameter,
+        class_info: &ClassDefinition,
+    ) -> Result<()> {
+        let mut annotations = param
+            .annotations
+            .iter()
+            .filter(|annotation| annotation.is_diagnostics_property_class_annotation());
+
+        let Some(annotation) = annotations.next() else {
+            return Ok(());
+        };
+
+        if annotations.next().is_some() {
+            return Err(anyhow!(
+                "Multiple @DiagnosticsPropertyClass annotations found for parameter {} in class {}",
+                param.name,
+                class_info.name
+            ));
+        }
+
+        let valid = annotation
+            .string_literal_parameter()
+            .map(|name| DiagnosticsPropertyClass::from_name(&name).is_some())
+            .unwrap_or_default();
+
+        if !valid {
+            return Err(anyhow!(
+                "@DiagnosticsPropertyClass annotation for parameter {} in class {} must have one of the following values as a string literal: {}",
+                param.name,
+                class_info.name,
+                DiagnosticsPropertyClass::ALL
+                    .iter()
+                    .map(|c| c.name())
+                    .collect::<Vec<&str>>()
+                    .join(", ")
+            ));
         }
 
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::validate_class;
+
+    fn error(source: &str) -> String {
+        match validate_class(source) {
+            Ok(_) => panic!("class is valid"),
+            Err(e) => e.to_string(),
+        }
+    }
+
+    #[test]
+    fn redirect_target_clashes_with_generated_names() {
+        for target in ["A", "_$A", "_$AImpl"] {
+            assert_eq!(
+                error(&format!(
+                    "@freezed class A with _$A {{ factory A({{int a}}) = {}; }}",
+                    target
+                )),
+                format!(
+                    "Factory constructor in class A redirects to {}, which is reserved. Use _A instead.",
+                    target
+                )
+            );
+        }
+        assert!(validate_class("@freezed class A with _$A { factory A({int a}) = _A; }").is_ok());
+    }
+
+    #[test]
+    fn malformed_assert_makes_failure() {
+        for parameters in [
+            "",
+            "a > 0",
+            "'a > 0', message: 'm'",
+            "' '",
+            "'a > 0', 'm', 'n'",
+        ] {
+            assert_eq!(
+                error(&format!(
+                    "@freezed class A with _$A {{ @Assert({}) factory A({{int a}}) = _A; }}",
+                    parameters
+                )),
+                format!(
+                    "Invalid @Assert({}) annotation for factory constructor in class A. Use @Assert('expression') or @Assert('expression', 'message').",
+                    parameters
+                )
+            );
+        }
+        assert!(validate_class(
+            "@freezed class A with _$A { @Assert('a > 0', 'm') factory A({int a}) = _A; }"
+        )
+        .is_ok());
+    }
+
+    #[test]
+    fn invalid_type_annotation_makes_failure() {
+        for (name, annotation) in [
+            ("With", "@With()"),
+            ("With", "@With<B>(1)"),
+            ("Implements", "@Implements.fromString()"),
+            ("Implements", "@Implements.fromString(' ')"),
+            ("Implements", "@Implements.fromString(type: 'B')"),
+            ("Implements", "@Implements.other('B')"),
+        ] {
+            assert_eq!(
+                error(&format!(
+                    "@freezed class A with _$A {{ {} factory A({{int a}}) = _A; }}",
+                    annotation
+                )),
+                format!(
+                    "Invalid @{} annotation for factory constructor in class A. Use @{}<Type>() or @{}.fromString('Type').",
+                    name, name, name
+                ),
+                "{}",
+                annotation
+            );
+        }
+        assert!(validate_class(
+            "@freezed class A with _$A { @With<B>() @Implements.fromString('C') factory A({int a}) = _A; }"
+        )
+        .is_ok());
+    }
+
+    #[test]
+    fn invalid_parameter_names_make_failure() {
+        assert_eq!(
+            error("@freezed class A with _$A { factory A({int a, int a}) = _A; }"),
+            "Parameter a is defined multiple times in factory constructor of class A"
+        );
+        assert_eq!(
+            error("@freezed class A with _$A { factory A({int hashCode}) = _A; }"),
+            "Parameter hashCode in class A clashes with member hashCode of the generated class. Rename the parameter."
+        );
+        assert_eq!(
+            error(
+                "@freezed class A with _$A { factory A({@Default(1) int a, int _aDefaultValue}) = _A; }"
+            ),
+            "Parameter _aDefaultValue in class A clashes with identifier _aDefaultValue of the generated code. Rename the parameter."
+        );
+        assert!(validate_class(
+            "@freezed class A with _$A { factory A({int a, int _aDefaultValue}) = _A; }"
+        )
+        .is_ok());
+    }
+
+    fn error_location(source: &str) -> Location {
+        validate_class(source)
+            .err()
+            .and_then(|e| e.downcast::<LocatedError>().ok())
+            .map(|e| e.location)
+            .expect("located error")
+    }
+
+    #[test]
+    fn missing_mixin_makes_failure() {
+        let source = "@freezed\nclass A {\n  factory A({int a}) = _A;\n}";
+        assert_eq!(
+            error(source),
+            "Class A does not mix in the generated mixin _$A. Add `with _$A` after the class name."
+        );
+        assert_eq!(error_location(source), Location { line: 2, column: 7 });
+        assert!(
+            validate_class("@freezed class A with B, _$A { factory A({int a}) = _A; }").is_ok()
+        );
+    }
+
+    #[test]
+    fn mixin_with_type_arguments_makes_failure() {
+        assert_eq!(
+            error("@freezed class A with _$A<int> { factory A({int a}) = _A; }"),
+            "Type arguments of mixin `_$A<int>` do not match the type parameters of class A. Replace it with `_$A`."
+        );
+    }
+
+    #[test]
+    fn custom_members_require_private_constructor() {
+        let source =
+            "@freezed\nclass A with _$A {\n  const factory A({int a}) = _A;\n  int get b => a;\n}";
+        assert_eq!(
+            error(source),
+            "Class A has custom members, but no private constructor. Add `const A._();` to the class body."
+        );
+        assert_eq!(error_location(source), Location { line: 4, column: 3 });
+        assert!(validate_class(
+            "@freezed class A with _$A { const A._(); const factory A({int a}) = _A; int get b => a; }"
+        )
+        .is_ok());
+    }
+
+    #[test]
+    fn static_members_do_not_require_private_constructor() {
+        assert!(validate_class(
+            "@freezed class A with _$A { static const b = 1; factory A({int a}) = _A; static A c() => A(a: b); }"
+        )
+        .is_ok());
+    }
+}
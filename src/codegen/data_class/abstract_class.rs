@@ -1,7 +1,7 @@
 use anyhow::Result;
 use indoc::formatdoc;
 
-use crate::codegen::utils::indent_lines;
+use crate::codegen::utils::{generate_metadata, generate_parameter_list, indent_lines};
 
 use super::ValidatedClass;
 
@@ -19,7 +19,11 @@ pub fn generate_abstract_class(class: &ValidatedClass) -> Result<String> {
             ""
         };
 
-        format!("\n  {}_{}._() : super._();", const_keyword, class.name,)
+        format!(
+            "\n  {}{}._() : super._();",
+            const_keyword,
+            class.abstract_class_name(),
+        )
     } else {
         "".to_string()
     };
@@ -27,10 +31,10 @@ pub fn generate_abstract_class(class: &ValidatedClass) -> Result<String> {
     let abstract_class = formatdoc!(
         "
         /// @nodoc
-        abstract class _{} {} {} {{
+        abstract class {} {} {} {{
         {}{}
         }}",
-        class.name,
+        class.abstract_class_name(),
         class_modifier,
         class.name,
         indent_lines("  ", generate_abstract_class_factory(class)?),
@@ -47,31 +51,20 @@ fn generate_abstract_class_factory(class: &ValidatedClass) -> Result<String> {
         ""
     };
 
-    let factory = formatdoc!(
-        "
-        {}factory _{}({{
-        {}
-        }}) = _${}Impl;",
+    let factory = format!(
+        "{}{}factory {}{} = _${}Impl;",
+        generate_metadata(
+            class.factory_constructor_doc_comment(),
+            class.factory_constructor_deprecated_annotation(),
+        ),
         const_keyword,
-        class.name,
-        indent_lines("  ", generate_abstract_class_field_params(class)),
+        class.abstract_class_name(),
+        generate_parameter_list(class.factory_constructor_params(), |field| {
+            let required = if field.required { "required " } else { "" };
+            format!("{}{} {}", required, field.parameter_type, field.name)
+        }),
         class.name,
     );
 
     Ok(factory)
 }
-
-fn generate_abstract_class_field_params(class: &ValidatedClass) -> String {
-    let mut field_getters = String::new();
-
-    for field in class.factory_constructor_params() {
-        let required = if field.required { "required " } else { "" };
-
-        field_getters.push_str(&format!(
-            "{}{} {},\n",
-            required, field.parameter_type, field.name,
-        ));
-    }
-
-    field_getters
-}
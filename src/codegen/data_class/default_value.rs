@@ -0,0 +1,160 @@
+use crate::parser::{expression_kind, DataType, ExpressionKind, Parameter};
+
+/// Types from `dart:core` which do not have const constructors
+const NON_CONST_CORE_TYPES: &[&str] = &["DateTime", "RegExp", "StringBuffer", "Stopwatch", "Uri"];
+
+/// Functions which can be invoked in constant expressions
+const CONST_FUNCTIONS: &[&str] = &["identical"];
+
+/// Types which accept only some literals
+const LITERAL_TYPES: &[&str] = &[
+    "bool", "int", "double", "num", "String", "List", "Set", "Map", "Iterable",
+];
+
+/// Check that `@Default` value of `field` can be used as value of
+/// a `static const` variable with the type of the field.
+/// Returns the reason if the value is not valid.
+pub fn default_value_error(field: &Parameter, value: &str) -> Option<String> {
+    let kind = expression_kind(value);
+
+    if let ExpressionKind::Invocation {
+        new_keyword, name, ..
+    } = &kind
+    {
+        if *new_keyword {
+            return Some("`new` creates a non-constant object".to_string());
+        }
+
+        if let Some(core_type) = name
+            .iter()
+            .find(|name| NON_CONST_CORE_TYPES.contains(&name.as_str()))
+        {
+            return Some(format!("{} does not have const constructors", core_type));
+        }
+
+        let function_invocation = name
+            .iter()
+            .all(|name| !name.as_str().starts_with(char::is_uppercase));
+        let const_function = name.len() == 1 && CONST_FUNCTIONS.contains(&name[0].as_str());
+        if function_invocation && !const_function {
+            return Some("function invocation is not a constant expression".to_string());
+        }
+    }
+
+    if literal_assignable(&kind, &field.parameter_type) {
+        None
+    } else {
+        Some(format!(
+            "{} can not be assigned to type {}",
+            kind.description(),
+            field.parameter_type,
+        ))
+    }
+}
+
+/// Returns false only if `kind` is a literal which is known
+/// to be not assignable to `data_type`.
+fn literal_assignable(kind: &ExpressionKind, data_type: &DataType) -> bool {
+    if !kind.is_literal() {
+        return true;
+    }
+
+    if *kind == ExpressionKind::Null {
+        return data_type.is_nullable();
+    }
+
+    let type_name = match data_type {
+        DataType::Named { name, .. } | DataType::Generic { name, .. } => name.as_str(),
+        DataType::Function(_) | DataType::Record(_) => return false,
+        DataType::Prefixed { .. } | DataType::Dynamic | DataType::Void => return true,
+    };
+
+    if !LITERAL_TYPES.contains(&type_name) {
+        return true;
+    }
+
+    let assignable_types: &[&str] = match kind {
+        ExpressionKind::Bool => &["bool"],
+        ExpressionKind::Int => &["int", "double", "num"],
+        ExpressionKind::Double => &["double", "num"],
+        ExpressionKind::String => &["String"],
+        ExpressionKind::List => &["List", "Iterable"],
+        ExpressionKind::Set => &["Set", "Iterable"],
+        ExpressionKind::Map => &["Map"],
+        _ => return true,
+    };
+
+    assignable_types.contains(&type_name)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::parser::{ClassItem, ParsedFile, TopLevelItems};
+
+    use super::*;
+
+    fn error(parameter_type: &str, value: &str) -> Option<String> {
+        let parsed_file = ParsedFile::parse_dart_file(&format!(
+            "class A {{ factory A({{{} a}}) = _A; }}",
+            parameter_type
+        ))
+        .unwrap();
+        match &parsed_file.items[..] {
+            [TopLevelItems::Class(class)] => match &class.item_info.items[..] {
+                [ClassItem::FactoryConstructor(constructor)] => {
+                    default_value_error(&constructor.params[0], value)
+                }
+                _ => panic!("factory constructor not parsed"),
+            },
+            _ => panic!("class not parsed"),
+        }
+    }
+
+    #[test]
+    fn non_const_invocations_are_not_valid() {
+        assert_eq!(
+            error("DateTime", "DateTime.now()"),
+            Some("DateTime does not have const constructors".to_string())
+        );
+        assert_eq!(
+            error("B", "new B()"),
+            Some("`new` creates a non-constant object".to_string())
+        );
+        assert_eq!(
+            error("int", "f()"),
+            Some("function invocation is not a constant expression".to_string())
+        );
+        assert_eq!(error("B", "B()"), None);
+        assert_eq!(error("bool", "identical(1, 1)"), None);
+    }
+
+    #[test]
+    fn list_literals_with_and_without_const_are_valid() {
+        assert_eq!(error("List<int>", "[]"), None);
+        assert_eq!(error("List<int>", "const []"), None);
+        assert_eq!(error("Iterable<int>", "const <int>[1]"), None);
+        assert_eq!(
+            error("Set<int>", "const []"),
+            Some("a list literal can not be assigned to type Set<int>".to_string())
+        );
+    }
+
+    #[test]
+    fn literals_are_assignable_to_their_types() {
+        assert_eq!(error("double", "1"), None);
+        assert_eq!(error("int?", "null"), None);
+        assert_eq!(error("B", "'b'"), None);
+        assert_eq!(
+            error("int", "null"),
+            Some("null can not be assigned to type int".to_string())
+        );
+        assert_eq!(
+            error("int", "1.5"),
+            Some("a double literal can not be assigned to type int".to_string())
+        );
+        assert_eq!(
+            error("Map<String, int>", "{1}"),
+            Some("a set literal can not be assigned to type Map<String, int>".to_string())
+        );
+    }
+}
@@ -13,66 +13,104 @@ mod fields;
 mod hash_code;
 mod to_string;
 
-pub use copy_with::generate_detect_default_class_and_constant;
+pub use copy_with::{generate_detect_default_class_and_constant, DEFAULT_DETECTOR_VARIABLE};
+pub use debug_fill_properties::DiagnosticsPropertyClass;
 
 pub fn generate_impl_class(file: &ValidatedFile, class: &ValidatedClass) -> Result<String> {
-    let abstract_class_name = format!("_{}", class.name);
-    let class_modifier = if class.private_constructor_exists() {
-        if file.flutter_foundation_import_exists {
-            format!(
-                "extends {} with DiagnosticableTreeMixin",
-                abstract_class_name
-            )
-        } else {
-            format!("extends {}", abstract_class_name)
-        }
-    } else if file.flutter_foundation_import_exists {
-        format!(
-            "with DiagnosticableTreeMixin implements {}",
-            abstract_class_name
-        )
-    } else {
-        format!("implements {}", abstract_class_name)
-    };
+    let abstract_class_name = class.abstract_class_name().to_string();
+
+    let mut mixins = vec![];
+    let mut interfaces = vec![];
 
-    let debug_fill_properties = if file.flutter_foundation_import_exists {
-        format!(
-            "\n\n{}",
-            indent_lines(
-                "  ",
-                debug_fill_properties::generate_debug_fill_properties(file, class)
-            )
-        )
+    if file.flutter_foundation_import_exists {
+        mixins.push("DiagnosticableTreeMixin".to_string());
+    }
+    mixins.extend(class.additional_mixins().iter().cloned());
+
+    let mut class_modifier = if class.private_constructor_exists() {
+        format!("extends {}", abstract_class_name)
     } else {
+        interfaces.push(abstract_class_name);
         "".to_string()
     };
+    interfaces.extend(class.additional_interfaces().iter().cloned());
 
-    let impl_class = formatdoc!(
-        "
-        /// @nodoc
-        class _${}Impl {} {{
-        {}
+    for (keyword, types) in [("with", mixins), ("implements", interfaces)] {
+        if !types.is_empty() {
+            if !class_modifier.is_empty() {
+                class_modifier.push(' ');
+            }
+            class_modifier.push_str(&format!("{} {}", keyword, types.join(", ")));
+        }
+    }
 
-        {}
+    let options = class.options();
+    let mut sections = vec![
+        constructor::generate_impl_class_constructor(class)?,
+        fields::generate_impl_class_overridden_fields(class),
+    ];
 
-        {}{}
+    if options.to_string_override {
+        sections.push(to_string::generate_impl_class_to_string(file, class));
+    }
 
-        {}
+    if file.flutter_foundation_import_exists {
+        sections.push(debug_fill_properties::generate_debug_fill_properties(
+            file, class,
+        ));
+    }
 
-        {}
+    if options.equal {
+        sections.push(equality::generate_impl_class_equality_operator(class));
+        sections.push(hash_code::generate_impl_class_hash_code(class));
+    }
+
+    if options.copy_with {
+        sections.push(copy_with::generate_impl_class_copy_with(class));
+    }
 
+    let sections = sections
+        .iter()
+        .map(|section| indent_lines("  ", section.trim_end()))
+        .collect::<Vec<String>>()
+        .join("\n\n");
+
+    let impl_class = formatdoc!(
+        "
+        /// @nodoc
+        class _${}Impl {} {{
         {}
         }}",
         class.name,
         class_modifier,
-        indent_lines("  ", constructor::generate_impl_class_constructor(class)?),
-        indent_lines("  ", fields::generate_impl_class_overridden_fields(class)),
-        indent_lines("  ", to_string::generate_impl_class_to_string(file, class)),
-        debug_fill_properties,
-        indent_lines("  ", equality::generate_impl_class_equality_operator(class)),
-        indent_lines("  ", hash_code::generate_impl_class_hash_code(class)),
-        indent_lines("  ", copy_with::generate_impl_class_copy_with(class)),
+        sections,
     );
 
     Ok(impl_class)
 }
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use crate::codegen::generate_data_class_file;
+    use crate::diagnosticable::DiagnosticableLibraries;
+    use crate::file_finder::DartFile;
+
+    fn generate(source: &str) -> String {
+        let file = DartFile::parse(Path::new("a.dart"), source).unwrap();
+        generate_data_class_file(&file, &DiagnosticableLibraries::new(&[], false)).unwrap()
+    }
+
+    #[test]
+    fn unfreezed_class_has_no_equality() {
+        let generated =
+            generate("@unfreezed class A with _$A { factory A({required int a}) = _A; }");
+        assert!(!generated.contains("operator =="));
+        assert!(!generated.contains("hashCode"));
+
+        let generated = generate("@freezed class A with _$A { factory A({required int a}) = _A; }");
+        assert!(generated.contains("bool operator ==(Object other)"));
+        assert!(generated.contains("int get hashCode"));
+    }
+}
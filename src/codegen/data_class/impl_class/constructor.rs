@@ -1,7 +1,11 @@
+use crate::{
+    codegen::{
+        data_class::ValidatedClass,
+        utils::{generate_metadata, generate_parameter_list},
+    },
+    parser::Parameter,
+};
 use anyhow::Result;
-use indoc::formatdoc;
-
-use crate::codegen::{data_class::ValidatedClass, utils::indent_lines};
 
 pub fn generate_impl_class_constructor(class: &ValidatedClass) -> Result<String> {
     let const_keyword = if class.factory_constructor_is_const() {
@@ -10,46 +14,55 @@ pub fn generate_impl_class_constructor(class: &ValidatedClass) -> Result<String>
         ""
     };
 
-    let super_constructor_invocation = if class.private_constructor_exists() {
-        " : super._()"
+    let mut initializers = vec![];
+
+    for assertion in class.factory_constructor_assertions() {
+        if let Some(message) = &assertion.message {
+            initializers.push(format!("assert({}, {})", assertion.expression, message));
+        } else {
+            initializers.push(format!("assert({})", assertion.expression));
+        }
+    }
+
+    if class.private_constructor_exists() {
+        initializers.push("super._()".to_string());
+    }
+
+    let initializer_list = if initializers.is_empty() {
+        "".to_string()
     } else {
-        ""
+        format!(" : {}", initializers.join(", "))
     };
 
-    let factory = formatdoc!(
-        "
-        {}{}_${}Impl({{
-        {}
-        }}){};",
+    let factory = format!(
+        "{}{}{}_${}Impl{}{};",
         const_values_for_field_value_defaults(class),
+        generate_metadata(
+            class.factory_constructor_doc_comment(),
+            class.factory_constructor_deprecated_annotation(),
+        ),
         const_keyword,
         class.name,
-        indent_lines("  ", generate_impl_class_field_params(class)),
-        super_constructor_invocation,
+        generate_parameter_list(
+            class.factory_constructor_params(),
+            generate_impl_class_field_param
+        ),
+        initializer_list,
     );
 
     Ok(factory)
 }
 
-fn generate_impl_class_field_params(class: &ValidatedClass) -> String {
-    let mut field_getters = String::new();
+fn generate_impl_class_field_param(field: &Parameter) -> String {
+    let required = if field.required { "required " } else { "" };
 
-    for field in class.factory_constructor_params() {
-        let required = if field.required { "required " } else { "" };
-
-        let default_value = if field.default_annotation().is_some() {
-            format!(" = _{}DefaultValue", field.name)
-        } else {
-            "".to_string()
-        };
-
-        field_getters.push_str(&format!(
-            "{}this.{}{},\n",
-            required, field.name, default_value,
-        ));
-    }
+    let default_value = if field.default_annotation().is_some() {
+        format!(" = _{}DefaultValue", field.name)
+    } else {
+        "".to_string()
+    };
 
-    field_getters
+    format!("{}this.{}{}", required, field.name, default_value)
 }
 
 fn const_values_for_field_value_defaults(class: &ValidatedClass) -> String {
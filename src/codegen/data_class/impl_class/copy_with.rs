@@ -2,7 +2,7 @@ use indoc::formatdoc;
 
 use crate::codegen::{data_class::ValidatedClass, utils::indent_lines};
 
-const DEFAULT_DETECTOR_VARIABLE: &str = "_detectDefaultValueInCopyWith";
+pub const DEFAULT_DETECTOR_VARIABLE: &str = "_detectDefaultValueInCopyWith";
 
 pub fn generate_detect_default_class_and_constant() -> String {
     formatdoc!(
@@ -38,13 +38,21 @@ fn generate_field_params(class: &ValidatedClass) -> String {
     let mut fields = String::new();
 
     for field in class.factory_constructor_params() {
-        let default_value = if field.parameter_type.nullable {
+        let default_value = if field.parameter_type.is_nullable() {
             format!(" = {}", DEFAULT_DETECTOR_VARIABLE)
         } else {
             String::new()
         };
 
-        fields.push_str(&format!("Object? {}{},\n", field.name, default_value,));
+        let annotations = field
+            .member_annotations()
+            .map(|a| format!("{} ", a))
+            .collect::<String>();
+
+        fields.push_str(&format!(
+            "{}Object? {}{},\n",
+            annotations, field.name, default_value,
+        ));
     }
 
     fields
@@ -54,10 +62,16 @@ fn generate_field_args(class: &ValidatedClass) -> String {
     let mut fields = String::new();
 
     for field in class.factory_constructor_params() {
-        let arg = if field.parameter_type.nullable {
+        let arg_name = if field.is_named() {
+            format!("{}: ", field.name)
+        } else {
+            String::new()
+        };
+
+        let arg = if field.parameter_type.is_nullable() {
             format!(
-                "{}: ({} == {} ? this.{} : {}) as {},\n",
-                field.name,
+                "{}({} == {} ? this.{} : {}) as {},\n",
+                arg_name,
                 field.name,
                 DEFAULT_DETECTOR_VARIABLE,
                 field.name,
@@ -66,8 +80,8 @@ fn generate_field_args(class: &ValidatedClass) -> String {
             )
         } else {
             format!(
-                "{}: ({} ?? this.{}) as {},\n",
-                field.name, field.name, field.name, field.parameter_type,
+                "{}({} ?? this.{}) as {},\n",
+                arg_name, field.name, field.name, field.parameter_type,
             )
         };
 
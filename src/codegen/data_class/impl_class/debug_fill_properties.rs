@@ -1,6 +1,92 @@
 use indoc::formatdoc;
 
-use crate::codegen::{data_class::ValidatedClass, utils::indent_lines, ValidatedFile};
+use crate::{
+    codegen::{
+        data_class::ValidatedClass,
+        utils::{dart_string_literal, field_reference, indent_lines},
+        ValidatedFile,
+    },
+    parser::{DataType, Parameter},
+};
+
+/// Diagnostics property class from `package:flutter/foundation.dart` or
+/// `package:flutter/painting.dart`.
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub enum DiagnosticsPropertyClass {
+    Diagnostics,
+    Int,
+    Double,
+    String,
+    Flag,
+    Enum,
+    Iterable,
+    Color,
+}
+
+impl DiagnosticsPropertyClass {
+    pub const ALL: &'static [DiagnosticsPropertyClass] = &[
+        Self::Diagnostics,
+        Self::Int,
+        Self::Double,
+        Self::String,
+        Self::Flag,
+        Self::Enum,
+        Self::Iterable,
+        Self::Color,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Diagnostics => "DiagnosticsProperty",
+            Self::Int => "IntProperty",
+            Self::Double => "DoubleProperty",
+            Self::String => "StringProperty",
+            Self::Flag => "FlagProperty",
+            Self::Enum => "EnumProperty",
+            Self::Iterable => "IterableProperty",
+            Self::Color => "ColorProperty",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.iter().copied().find(|c| c.name() == name)
+    }
+
+    /// Select property class using the annotation
+    /// `@DiagnosticsPropertyClass('...')` or the parameter type.
+    /// `DiagnosticsProperty` is used if the imports do not make the
+    /// selected class visible.
+    fn select(file: &ValidatedFile, field: &Parameter) -> Self {
+        let selected = Self::select_with_annotation(field).unwrap_or_else(|| {
+            let data_type = &field.parameter_type;
+            let prefixed = matches!(data_type, DataType::Prefixed { .. });
+
+            match data_type.base_name() {
+                Some("int") => Self::Int,
+                Some("double") => Self::Double,
+                Some("String") => Self::String,
+                Some("bool") => Self::Flag,
+                Some("List" | "Set" | "Iterable") => Self::Iterable,
+                Some("Color") => Self::Color,
+                Some(name) if !prefixed && file.enum_names.contains(name) => Self::Enum,
+                _ => Self::Diagnostics,
+            }
+        });
+
+        if file.visible_property_classes.contains(&selected) {
+            selected
+        } else {
+            Self::Diagnostics
+        }
+    }
+
+    fn select_with_annotation(field: &Parameter) -> Option<Self> {
+        field
+            .diagnostics_property_class_annotation()
+            .and_then(|annotation| annotation.string_literal_parameter())
+            .and_then(|name| Self::from_name(&name))
+    }
+}
 
 pub fn generate_debug_fill_properties(file: &ValidatedFile, class: &ValidatedClass) -> String {
     if !file.flutter_foundation_import_exists {
@@ -9,10 +95,7 @@ pub fn generate_debug_fill_properties(file: &ValidatedFile, class: &ValidatedCla
 
     let mut fields = String::new();
     for field in class.factory_constructor_params() {
-        fields.push_str(&format!(
-            "..add(DiagnosticsProperty('{}', {}))\n",
-            field.name, field.name,
-        ));
+        fields.push_str(&format!("..add({})\n", generate_property(file, field)));
     }
     fields.pop();
 
@@ -22,12 +105,144 @@ pub fn generate_debug_fill_properties(file: &ValidatedFile, class: &ValidatedCla
         void debugFillProperties(DiagnosticPropertiesBuilder properties) {{
           super.debugFillProperties(properties);
           properties
-            ..add(DiagnosticsProperty('type', '{}'))
+            ..add(DiagnosticsProperty('type', {}))
         {};
         }}",
-        class.name,
+        dart_string_literal(class.name.as_str()),
         indent_lines("    ", fields),
     );
 
     function
 }
+
+const PROPERTIES_PARAMETER: &str = "properties";
+
+fn generate_property(file: &ValidatedFile, field: &Parameter) -> String {
+    let property_class = DiagnosticsPropertyClass::select(file, field);
+    let class_name = property_class.name();
+    // Field named `properties` is shadowed by the parameter
+    let value = field_reference(&field.name, &[PROPERTIES_PARAMETER]);
+    let name = dart_string_literal(field.name.as_str());
+
+    // Properties with default value are shown only
+    // when the value is not the default value.
+    let default_value = if field.default_annotation().is_some() {
+        format!(", defaultValue: _{}DefaultValue", field.name)
+    } else if field.parameter_type.is_nullable() {
+        ", defaultValue: null".to_string()
+    } else {
+        "".to_string()
+    };
+
+    match property_class {
+        DiagnosticsPropertyClass::Flag => format!(
+            "{}({}, value: {}, ifTrue: {}, ifFalse: {}{})",
+            class_name,
+            name,
+            value,
+            dart_string_literal(&format!("{}: true", field.name)),
+            dart_string_literal(&format!("{}: false", field.name)),
+            default_value,
+        ),
+        DiagnosticsPropertyClass::Enum => format!(
+            "{}<{}>({}, {}{})",
+            class_name,
+            field.parameter_type.to_non_nullable(),
+            name,
+            value,
+            default_value,
+        ),
+        DiagnosticsPropertyClass::Iterable => {
+            let item_type = field
+                .parameter_type
+                .type_args()
+                .first()
+                .map(|t| t.to_string())
+                .unwrap_or_else(|| DataType::Dynamic.to_string());
+            format!(
+                "{}<{}>({}, {}{})",
+                class_name, item_type, name, value, default_value,
+            )
+        }
+        DiagnosticsPropertyClass::Diagnostics
+        | DiagnosticsPropertyClass::Int
+        | DiagnosticsPropertyClass::Double
+        | DiagnosticsPropertyClass::String
+        | DiagnosticsPropertyClass::Color => {
+            format!("{}({}, {}{})", class_name, name, value, default_value,)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use crate::{
+        codegen::generate_data_class_file, diagnosticable::DiagnosticableLibraries,
+        file_finder::DartFile,
+    };
+
+    fn generate(fields: &str) -> String {
+        generate_with_import("import 'package:flutter/foundation.dart';", fields)
+    }
+
+    fn generate_with_import(import: &str, fields: &str) -> String {
+        let source = format!(
+            "{}
+
+            part 'a.freezed.dart';
+
+            @freezed
+            class A with _$A {{
+              factory A({{{}}}) = _A;
+            }}",
+            import, fields
+        );
+        let file = DartFile::parse(Path::new("a.dart"), &source).unwrap();
+        generate_data_class_file(&file, &DiagnosticableLibraries::new(&[], false)).unwrap()
+    }
+
+    #[test]
+    fn property_class_is_selected_with_annotation() {
+        let generated = generate(
+            "@DiagnosticsPropertyClass('StringProperty') required Object name,
+            @DiagnosticsPropertyClass('FlagProperty') required bool visible,
+            required int count",
+        );
+
+        assert!(generated.contains("..add(StringProperty('name', name))"));
+        assert!(generated.contains(
+            "..add(FlagProperty('visible', value: visible, ifTrue: 'visible: true', ifFalse: 'visible: false'))"
+        ));
+        assert!(generated.contains("..add(IntProperty('count', count))"));
+    }
+
+    #[test]
+    fn property_classes_which_are_not_imported_are_not_used() {
+        let generated = generate_with_import(
+            "import 'package:flutter/foundation.dart' show DiagnosticableTreeMixin, DiagnosticLevel, DiagnosticPropertiesBuilder, DiagnosticsProperty;",
+            "required int count,
+            required double ratio,
+            required String name,
+            required bool visible,
+            required List<int> items,
+            required Color color,
+            @DiagnosticsPropertyClass('StringProperty') required Object title",
+        );
+
+        for field in [
+            "count", "ratio", "name", "visible", "items", "color", "title",
+        ] {
+            assert!(
+                generated.contains(&format!(
+                    "..add(DiagnosticsProperty('{}', {}))",
+                    field, field
+                )),
+                "{}",
+                field
+            );
+        }
+        assert!(!generated.contains("IntProperty"));
+    }
+}
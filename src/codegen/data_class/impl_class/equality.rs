@@ -1,6 +1,15 @@
 use indoc::formatdoc;
 
-use crate::codegen::{data_class::ValidatedClass, utils::indent_lines};
+use crate::codegen::{
+    data_class::ValidatedClass,
+    utils::{field_reference, indent_lines},
+};
+
+const OTHER_PARAMETER: &str = "other";
+
+/// Compares collections by their elements. It is exported by
+/// `package:freezed_annotation`.
+pub const DEEP_COLLECTION_EQUALITY: &str = "const DeepCollectionEquality()";
 
 pub fn generate_impl_class_equality_operator(class: &ValidatedClass) -> String {
     let mut equality_checks = String::new();
@@ -8,16 +17,25 @@ pub fn generate_impl_class_equality_operator(class: &ValidatedClass) -> String {
     for (i, field) in class.factory_constructor_params().iter().enumerate() {
         let field_count = class.factory_constructor_params().len();
         let and_operator = if i == field_count - 1 { "" } else { " &&" };
-        equality_checks.push_str(&formatdoc!(
-            "
-            (identical(other.{}, {}) ||
-              other.{} == {}){}\n",
-            field.name,
-            field.name,
-            field.name,
-            field.name,
-            and_operator,
-        ));
+        // Field named `other` is shadowed by the parameter
+        let field_reference = field_reference(&field.name, &[OTHER_PARAMETER]);
+        if field.parameter_type.is_collection() {
+            equality_checks.push_str(&format!(
+                "{}.equals(other.{}, {}){}\n",
+                DEEP_COLLECTION_EQUALITY, field.name, field_reference, and_operator,
+            ));
+        } else {
+            equality_checks.push_str(&formatdoc!(
+                "
+                (identical(other.{}, {}) ||
+                  other.{} == {}){}\n",
+                field.name,
+                field_reference,
+                field.name,
+                field_reference,
+                and_operator,
+            ));
+        }
     }
 
     let equality = formatdoc!(
@@ -36,3 +54,28 @@ pub fn generate_impl_class_equality_operator(class: &ValidatedClass) -> String {
 
     equality
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::class_with_params;
+
+    #[test]
+    fn collections_are_compared_deeply() {
+        let equality =
+            generate_impl_class_equality_operator(&class_with_params("int a, List<int>? other"));
+        assert_eq!(
+            equality,
+            "@override
+bool operator ==(Object other) {
+  return identical(this, other) ||
+    (other.runtimeType == runtimeType &&
+      other is _$AImpl &&
+      (identical(other.a, a) ||
+        other.a == a) &&
+      const DeepCollectionEquality().equals(other.other, this.other)
+  );
+}"
+        );
+    }
+}
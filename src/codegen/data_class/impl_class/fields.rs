@@ -1,15 +1,24 @@
 use indoc::formatdoc;
 
-use crate::codegen::data_class::ValidatedClass;
+use crate::codegen::{data_class::ValidatedClass, utils::generate_metadata};
 
 pub fn generate_impl_class_overridden_fields(class: &ValidatedClass) -> String {
     let mut items = String::new();
 
     for field in class.factory_constructor_params() {
+        let final_keyword = if class.field_is_final(field) {
+            "final "
+        } else {
+            ""
+        };
+
         items.push_str(&formatdoc!(
             "
-            @override
-            final {} {};\n",
+            {}@override
+            {}{}{} {};\n",
+            generate_metadata(&field.doc_comment, []),
+            generate_metadata(&[], field.member_annotations()),
+            final_keyword,
             field.parameter_type,
             field.name,
         ));
@@ -17,3 +26,22 @@ pub fn generate_impl_class_overridden_fields(class: &ValidatedClass) -> String {
 
     items
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::validate_class;
+
+    #[test]
+    fn unfreezed_class_has_mutable_fields_unless_final() {
+        let class = validate_class(
+            "@unfreezed class A with _$A { factory A({required int a, required final int b}) = _A; }",
+        )
+        .unwrap();
+
+        assert_eq!(
+            generate_impl_class_overridden_fields(&class),
+            "@override\nint a;\n@override\nfinal int b;\n"
+        );
+    }
+}
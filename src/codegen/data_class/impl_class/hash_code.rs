@@ -1,23 +1,111 @@
 use indoc::formatdoc;
 
+use super::equality::DEEP_COLLECTION_EQUALITY;
 use crate::codegen::{data_class::ValidatedClass, utils::indent_lines};
 
+/// Maximum argument count of `Object.hash`
+const OBJECT_HASH_MAX_ARGUMENTS: usize = 20;
+
+/// Generate `hashCode` which hashes fields with their `hashCode`
+/// like `operator ==` compares them with `==`. Collections are hashed
+/// by their elements.
 pub fn generate_impl_class_hash_code(class: &ValidatedClass) -> String {
+    let params = class.factory_constructor_params();
+
     let mut fields = String::new();
 
-    for field in class.factory_constructor_params() {
-        fields.push_str(&format!("{},\n", field.name,));
+    for field in params {
+        if field.parameter_type.is_collection() {
+            fields.push_str(&format!(
+                "{}.hash({}),\n",
+                DEEP_COLLECTION_EQUALITY, field.name
+            ));
+        } else {
+            fields.push_str(&format!("{},\n", field.name));
+        }
     }
 
-    let hash_code = formatdoc!(
-        "
-        @override
-        int get hashCode => Object.hash(
-          runtimeType,
-        {}
-        );",
-        indent_lines("  ", fields),
-    );
+    // Object.hash is also called with runtimeType
+    let hash_code = if params.len() + 1 > OBJECT_HASH_MAX_ARGUMENTS {
+        formatdoc!(
+            "
+            @override
+            int get hashCode => Object.hashAll([
+              runtimeType,
+            {}
+            ]);",
+            indent_lines("  ", fields),
+        )
+    } else {
+        formatdoc!(
+            "
+            @override
+            int get hashCode => Object.hash(
+              runtimeType,
+            {}
+            );",
+            indent_lines("  ", fields),
+        )
+    };
 
     hash_code
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::class_with_params;
+
+    fn validated_class(field_count: usize) -> ValidatedClass {
+        let params = (0..field_count)
+            .map(|i| format!("int a{},", i))
+            .collect::<Vec<String>>()
+            .join(" ");
+        class_with_params(&params)
+    }
+
+    #[test]
+    fn hash_code_30_fields_uses_hash_all() {
+        let hash_code = generate_impl_class_hash_code(&validated_class(30));
+        let fields = (0..30).map(|i| format!("  a{},\n", i)).collect::<String>();
+
+        assert_eq!(
+            hash_code,
+            format!(
+                "@override\nint get hashCode => Object.hashAll([\n  runtimeType,\n{}]);",
+                fields
+            )
+        );
+    }
+
+    #[test]
+    fn hash_code_19_fields_uses_hash() {
+        let hash_code = generate_impl_class_hash_code(&validated_class(19));
+        assert!(hash_code.starts_with("@override\nint get hashCode => Object.hash(\n"));
+        assert!(hash_code.ends_with("  a18,\n);"));
+    }
+
+    #[test]
+    fn hash_code_20_fields_uses_hash_all() {
+        let hash_code = generate_impl_class_hash_code(&validated_class(20));
+        assert!(hash_code.starts_with("@override\nint get hashCode => Object.hashAll([\n"));
+    }
+
+    #[test]
+    fn collections_are_hashed_deeply() {
+        let hash_code = generate_impl_class_hash_code(&class_with_params(
+            "int a, List<int> b, Set<int>? c, Map<String, int> d",
+        ));
+        assert_eq!(
+            hash_code,
+            "@override
+int get hashCode => Object.hash(
+  runtimeType,
+  a,
+  const DeepCollectionEquality().hash(b),
+  const DeepCollectionEquality().hash(c),
+  const DeepCollectionEquality().hash(d),
+);"
+        );
+    }
+}
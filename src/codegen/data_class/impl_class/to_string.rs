@@ -1,11 +1,27 @@
 use indoc::formatdoc;
 
-use crate::codegen::{data_class::ValidatedClass, ValidatedFile};
+use crate::codegen::{
+    data_class::ValidatedClass,
+    utils::{dart_string_interpolation, escape_dart_string, field_reference},
+    ValidatedFile,
+};
+
+const MIN_LEVEL_PARAMETER: &str = "minLevel";
 
 pub fn generate_impl_class_to_string(file: &ValidatedFile, class: &ValidatedClass) -> String {
+    let locals: &[&str] = if file.flutter_foundation_import_exists {
+        &[MIN_LEVEL_PARAMETER]
+    } else {
+        &[]
+    };
+
     let mut fields = String::new();
     for field in class.factory_constructor_params() {
-        fields.push_str(&format!("{}: ${}, ", field.name, field.name,));
+        fields.push_str(&format!(
+            "{}: {}, ",
+            escape_dart_string(field.name.as_str()),
+            dart_string_interpolation(&field_reference(&field.name, locals)),
+        ));
     }
     fields.pop();
     fields.pop();
@@ -23,7 +39,7 @@ pub fn generate_impl_class_to_string(file: &ValidatedFile, class: &ValidatedClas
           return '{}({})';
         }}",
         to_string_params,
-        class.name,
+        escape_dart_string(class.name.as_str()),
         fields,
     );
 
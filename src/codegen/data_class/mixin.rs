@@ -1,12 +1,11 @@
 use anyhow::Result;
 use indoc::formatdoc;
 
-use crate::{codegen::utils::indent_lines, parser::Identifier};
+use crate::codegen::utils::{escape_dart_string, generate_metadata, indent_lines};
 
 use super::ValidatedClass;
 
 pub fn generate_mixin(class: &ValidatedClass) -> Result<String> {
-    validate_class_name(&class.name)?;
     let exception_identifier = format!("_privateConstructorError{}", class.name);
     let variable_for_exception = formatdoc!(
         "
@@ -14,23 +13,30 @@ pub fn generate_mixin(class: &ValidatedClass) -> Result<String> {
         final {} = UnsupportedError(
             'Private constructor {}._() was called. Please call factory constructor instead.');",
         exception_identifier,
-        class.name,
+        escape_dart_string(class.name.as_str()),
     );
 
+    let copy_with = if class.options().copy_with {
+        format!(
+            "\n\n{}",
+            indent_lines("  ", generate_copy_with(class, &exception_identifier))
+        )
+    } else {
+        "".to_string()
+    };
+
     let mixin = formatdoc!(
         "
         {}
 
         /// @nodoc
         mixin _${} {{
-        {}
-
-        {}
+        {}{}
         }}",
         variable_for_exception,
         class.name,
         generate_field_getters_for_mixin(class, &exception_identifier),
-        indent_lines("  ", generate_copy_with(class, &exception_identifier)),
+        copy_with,
     );
 
     Ok(mixin)
@@ -40,26 +46,25 @@ fn generate_field_getters_for_mixin(class: &ValidatedClass, exception_identifier
     let mut field_getters = String::new();
 
     for field in class.factory_constructor_params() {
+        field_getters.push_str(&generate_metadata(
+            &field.doc_comment,
+            field.member_annotations(),
+        ));
         field_getters.push_str(&format!(
-            "  {} get {} => throw {};\n",
+            "{} get {} => throw {};\n",
             field.parameter_type, field.name, exception_identifier,
         ));
-    }
-    field_getters.pop();
-
-    field_getters
-}
 
-fn validate_class_name(name: &Identifier) -> Result<()> {
-    // Prevent formatting using class name
-    if name.as_str().contains('$') {
-        return Err(anyhow::anyhow!(
-            "Class name {} contains a dollar sign, which is not supported",
-            name
-        ));
+        if !class.field_is_final(field) {
+            field_getters.push_str(&generate_metadata(&[], field.deprecated_annotation()));
+            field_getters.push_str(&format!(
+                "set {}({} value) => throw {};\n",
+                field.name, field.parameter_type, exception_identifier,
+            ));
+        }
     }
 
-    Ok(())
+    indent_lines("  ", field_getters)
 }
 
 fn generate_copy_with_named_parameters(class: &ValidatedClass) -> String {
@@ -67,7 +72,14 @@ fn generate_copy_with_named_parameters(class: &ValidatedClass) -> String {
 
     for field in class.factory_constructor_params() {
         let nullable_type = field.parameter_type.to_nullable();
-        params.push_str(&format!("{} {},\n", nullable_type, field.name,));
+        let annotations = field
+            .member_annotations()
+            .map(|a| format!("{} ", a))
+            .collect::<String>();
+        params.push_str(&format!(
+            "{}{} {},\n",
+            annotations, nullable_type, field.name
+        ));
     }
 
     params
@@ -84,3 +96,31 @@ fn generate_copy_with(class: &ValidatedClass, exception_identifier: &str) -> Str
         exception_identifier,
     )
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::validate_class;
+
+    #[test]
+    fn unfreezed_class_has_setters_for_fields_which_are_not_final() {
+        let class = validate_class(
+            "@unfreezed class A with _$A { factory A({required int a, required final int b}) = _A; }",
+        )
+        .unwrap();
+        let mixin = generate_mixin(&class).unwrap();
+
+        assert!(mixin.contains("  int get a => throw _privateConstructorErrorA;\n"));
+        assert!(mixin.contains("  set a(int value) => throw _privateConstructorErrorA;\n"));
+        assert!(mixin.contains("  int get b => throw _privateConstructorErrorA;\n"));
+        assert!(!mixin.contains("set b("));
+    }
+
+    #[test]
+    fn freezed_class_has_no_setters() {
+        let class =
+            validate_class("@freezed class A with _$A { factory A({required int a}) = _A; }")
+                .unwrap();
+        assert!(!generate_mixin(&class).unwrap().contains("set a("));
+    }
+}
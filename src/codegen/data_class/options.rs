@@ -0,0 +1,197 @@
+use anyhow::{anyhow, Result};
+
+use crate::parser::{Annotation, Identifier};
+
+const COPY_WITH_OPTION: &str = "copyWith";
+const EQUAL_OPTION: &str = "equal";
+const TO_STRING_OVERRIDE_OPTION: &str = "toStringOverride";
+const ADD_IMPLICIT_FINAL_OPTION: &str = "addImplicitFinal";
+
+const NULL_VALUE: &str = "null";
+
+const SUPPORTED_OPTIONS: &[&str] = &[
+    COPY_WITH_OPTION,
+    EQUAL_OPTION,
+    TO_STRING_OVERRIDE_OPTION,
+    ADD_IMPLICIT_FINAL_OPTION,
+];
+
+/// Options from `@Freezed(...)` annotation
+#[derive(Debug, PartialEq, Clone)]
+pub struct FreezedOptions {
+    pub copy_with: bool,
+    pub equal: bool,
+    pub to_string_override: bool,
+    pub add_implicit_final: bool,
+}
+
+impl Default for FreezedOptions {
+    fn default() -> Self {
+        Self {
+            copy_with: true,
+            equal: true,
+            to_string_override: true,
+            add_implicit_final: true,
+        }
+    }
+}
+
+impl FreezedOptions {
+    /// Options for `@unfreezed` annotation, which creates mutable
+    /// data classes.
+    pub fn unfreezed() -> Self {
+        Self {
+            equal: false,
+            add_implicit_final: false,
+            ..Self::default()
+        }
+    }
+
+    pub fn from_annotation(annotation: &Annotation, class_name: &Identifier) -> Result<Self> {
+        let mut options = Self::default();
+
+        let arguments = annotation.arguments().ok_or(anyhow!(
+            "Invalid @{} annotation arguments for class {}",
+            annotation.name,
+            class_name
+        ))?;
+
+        let mut handled_options: Vec<String> = vec![];
+
+        for argument in arguments {
+            let name = argument.name.as_ref().ok_or(anyhow!(
+                "@{} annotation for class {} has positional argument {}, but only named arguments are supported",
+                annotation.name,
+                class_name,
+                argument.value,
+            ))?;
+
+            let option = match name.as_str() {
+                COPY_WITH_OPTION => &mut options.copy_with,
+                EQUAL_OPTION => &mut options.equal,
+                TO_STRING_OVERRIDE_OPTION => &mut options.to_string_override,
+                ADD_IMPLICIT_FINAL_OPTION => &mut options.add_implicit_final,
+                _ => {
+                    return Err(anyhow!(
+                        "Unknown option {} in @{} annotation for class {}. Supported options: {}",
+                        name,
+                        annotation.name,
+                        class_name,
+                        SUPPORTED_OPTIONS.join(", "),
+                    ))
+                }
+            };
+
+            if handled_options
+                .iter()
+                .any(|handled| handled == name.as_str())
+            {
+                return Err(anyhow!(
+                    "Option {} is set multiple times in @{} annotation for class {}",
+                    name,
+                    annotation.name,
+                    class_name,
+                ));
+            }
+            handled_options.push(name.to_string());
+
+            // Null uses the default value like in Freezed
+            if argument.value == NULL_VALUE {
+                continue;
+            }
+
+            *option = argument.bool_value().ok_or(anyhow!(
+                "Option {} in @{} annotation for class {} has value {}, but only boolean literals true and false and null are supported",
+                name,
+                annotation.name,
+                class_name,
+                argument.value,
+            ))?;
+        }
+
+        Ok(options)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::parser::{ParsedFile, TopLevelItems};
+
+    use super::*;
+
+    fn options(annotation: &str) -> Result<FreezedOptions> {
+        let parsed_file =
+            ParsedFile::parse_dart_file(&format!("{} class A {{}}", annotation)).unwrap();
+        match &parsed_file.items[..] {
+            [TopLevelItems::Class(class)] => {
+                FreezedOptions::from_annotation(&class.annotations[0], &class.name)
+            }
+            _ => panic!("class not parsed"),
+        }
+    }
+
+    #[test]
+    fn options_are_read() {
+        let default = FreezedOptions::default();
+        let cases = [
+            ("@Freezed()", default.clone()),
+            (
+                "@Freezed(copyWith: false)",
+                FreezedOptions {
+                    copy_with: false,
+                    ..default.clone()
+                },
+            ),
+            (
+                "@Freezed(equal: false, toStringOverride: false,)",
+                FreezedOptions {
+                    equal: false,
+                    to_string_override: false,
+                    ..default.clone()
+                },
+            ),
+            (
+                "@Freezed(addImplicitFinal: false)",
+                FreezedOptions {
+                    add_implicit_final: false,
+                    ..default.clone()
+                },
+            ),
+            ("@Freezed(copyWith: true, equal: null)", default.clone()),
+        ];
+
+        for (annotation, expected) in cases {
+            assert_eq!(options(annotation).unwrap(), expected, "{}", annotation);
+        }
+    }
+
+    #[test]
+    fn invalid_options_make_failure() {
+        let cases = [
+            (
+                "@Freezed(unionKey: 'type')",
+                "Unknown option unionKey in @Freezed annotation for class A. Supported options: copyWith, equal, toStringOverride, addImplicitFinal",
+            ),
+            (
+                "@Freezed(equal: false, equal: true)",
+                "Option equal is set multiple times in @Freezed annotation for class A",
+            ),
+            (
+                "@Freezed(false)",
+                "@Freezed annotation for class A has positional argument false, but only named arguments are supported",
+            ),
+            (
+                "@Freezed(copyWith: 1)",
+                "Option copyWith in @Freezed annotation for class A has value 1, but only boolean literals true and false and null are supported",
+            ),
+            (
+                "@Freezed(copyWith: )",
+                "Invalid @Freezed annotation arguments for class A",
+            ),
+        ];
+
+        for (annotation, expected) in cases {
+            assert_eq!(options(annotation).unwrap_err().to_string(), expected);
+        }
+    }
+}
@@ -0,0 +1,168 @@
+//! Header of generated code files
+//!
+//! The header contains the icegen version and a hash of the Dart file,
+//! so stale generated code files can be found without generating code.
+
+use super::GENERATED_FILE_HEADER;
+
+const GENERATOR_LINE_PREFIX: &str = "// icegen ";
+const SOURCE_HASH_SEPARATOR: &str = ", source hash ";
+
+/// Generator version and hash of the Dart file from the header of a
+/// generated code file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GeneratorInfo {
+    /// Build version like `1.2.0+abc1234`
+    pub version: String,
+    pub source_hash: String,
+}
+
+impl GeneratorInfo {
+    /// Info of code generated by this build from Dart file contents
+    /// `source`. The version identifies the build, see `build.rs`.
+    pub fn current(source: &str) -> Self {
+        Self {
+            version: env!("ICEGEN_BUILD_VERSION").to_string(),
+            source_hash: source_hash(source),
+        }
+    }
+
+    /// Version without the build metadata after `+`. Build metadata
+    /// identifies the commit of the build, which does not change the
+    /// generated code of a released version.
+    pub fn package_version(&self) -> &str {
+        self.version
+            .split_once('+')
+            .map_or(self.version.as_str(), |(version, _)| version)
+    }
+
+    fn parse(line: &str) -> Option<Self> {
+        let (version, source_hash) = line
+            .strip_prefix(GENERATOR_LINE_PREFIX)?
+            .split_once(SOURCE_HASH_SEPARATOR)?;
+
+        Some(Self {
+            version: version.to_string(),
+            source_hash: source_hash.to_string(),
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Header {
+    Current(GeneratorInfo),
+    /// Header without the generator info from older icegen versions
+    Legacy,
+    /// File was not generated by icegen or the header was modified
+    Unknown,
+}
+
+/// Header of generated code for Dart file contents `source`
+pub fn generated_file_header(source: &str) -> String {
+    let info = GeneratorInfo::current(source);
+    format!(
+        "{}{}{}{}{}\n",
+        GENERATED_FILE_HEADER,
+        GENERATOR_LINE_PREFIX,
+        info.version,
+        SOURCE_HASH_SEPARATOR,
+        info.source_hash
+    )
+}
+
+/// Parse the header of generated code. Line endings can be `\n` or
+/// `\r\n`.
+pub fn parse_header(generated_code: &str) -> Header {
+    let mut lines = generated_code.lines();
+    if !GENERATED_FILE_HEADER
+        .lines()
+        .all(|header_line| lines.next() == Some(header_line))
+    {
+        return Header::Unknown;
+    }
+
+    match lines.next().and_then(GeneratorInfo::parse) {
+        Some(info) => Header::Current(info),
+        None => Header::Legacy,
+    }
+}
+
+/// True if the generated code was not generated by this package version
+/// from Dart file contents `source`. Only the header is compared and
+/// the build metadata of the version is ignored.
+pub fn is_stale(source: &str, generated_code: &str) -> bool {
+    let current = GeneratorInfo::current(source);
+    match parse_header(generated_code) {
+        Header::Current(info) => {
+            info.package_version() != current.package_version()
+                || info.source_hash != current.source_hash
+        }
+        Header::Legacy | Header::Unknown => true,
+    }
+}
+
+/// 64-bit FNV-1a hash as hexadecimal. The hash does not change between
+/// platforms and Rust versions like the hashers of std can.
+pub(crate) fn source_hash(source: &str) -> String {
+    const OFFSET_BASIS: u64 = 0xcbf29ce484222325;
+    const PRIME: u64 = 0x100000001b3;
+
+    let hash = source.bytes().fold(OFFSET_BASIS, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(PRIME)
+    });
+    format!("{:016x}", hash)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn source_hash_is_fnv_1a() {
+        assert_eq!(source_hash(""), "cbf29ce484222325");
+        assert_eq!(source_hash("a"), "af63dc4c8601ec8c");
+    }
+
+    #[test]
+    fn generated_header_is_parsed() {
+        let code = format!(
+            "{}\npart of 'a.dart';\n",
+            generated_file_header("class A {}")
+        );
+        assert_eq!(
+            parse_header(&code),
+            Header::Current(GeneratorInfo::current("class A {}"))
+        );
+        assert!(!is_stale("class A {}", &code));
+        assert!(is_stale("class B {}", &code));
+        assert!(!is_stale("class A {}", &code.replace('\n', "\r\n")));
+    }
+
+    #[test]
+    fn legacy_and_unknown_headers() {
+        let legacy = format!("{}\npart of 'a.dart';\n", GENERATED_FILE_HEADER);
+        assert_eq!(parse_header(&legacy), Header::Legacy);
+        assert!(is_stale("class A {}", &legacy));
+        assert_eq!(parse_header("part of 'a.dart';\n"), Header::Unknown);
+    }
+
+    #[test]
+    fn build_metadata_does_not_make_code_stale() {
+        let code = generated_file_header("class A {}");
+        let info = GeneratorInfo::current("class A {}");
+        let package_version = env!("CARGO_PKG_VERSION");
+        assert_eq!(info.package_version(), package_version);
+
+        let other_build = code.replace(
+            &format!("icegen {}", info.version),
+            &format!("icegen {}+other-build", package_version),
+        );
+        assert!(!is_stale("class A {}", &other_build));
+
+        let other_version = code.replace(
+            &format!("icegen {}", info.version),
+            "icegen 999.0.0+other-build",
+        );
+        assert!(is_stale("class A {}", &other_version));
+    }
+}
@@ -0,0 +1,232 @@
+//! Check that Dart files include the generated code file with
+//! `part 'x.freezed.dart';`
+
+use std::path::Path;
+
+use crate::{
+    codegen::utils::dart_string_literal,
+    error::{Error, Location},
+    file_finder::{generated_code_path, DartFile},
+    parser::{SourcePosition, TopLevelItems},
+    source_text::LineEnding,
+};
+
+const GENERATED_CODE_FILE_SUFFIX: &str = ".freezed.dart";
+
+/// Replacement of the source code between two positions
+#[derive(Debug, Clone, PartialEq)]
+pub struct SourceEdit {
+    pub start: SourcePosition,
+    pub end: SourcePosition,
+    pub text: String,
+}
+
+impl SourceEdit {
+    pub fn apply(&self, source: &str) -> String {
+        let start = self.start.offset(source);
+        let end = self.end.offset(source);
+        format!("{}{}{}", &source[..start], self.text, &source[end..])
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PartDirectiveProblem {
+    pub message: String,
+    /// Position of the misspelt directive or the first annotated class
+    pub position: SourcePosition,
+    /// Short description of the fix like ``Add `part 'a.freezed.dart';` ``
+    pub fix_title: String,
+    pub fix: SourceEdit,
+}
+
+impl PartDirectiveProblem {
+    pub fn to_error(&self, file: &DartFile) -> Error {
+        let (line, column) = self.position.line_and_column(&file.contents);
+        Error::PartDirective {
+            path: file.path.clone(),
+            location: Location { line, column },
+            message: self.message.clone(),
+        }
+    }
+}
+
+fn generated_code_file_name(path: &Path) -> Option<String> {
+    let file_name = generated_code_path(path)
+        .file_name()?
+        .to_string_lossy()
+        .to_string();
+    Some(file_name)
+}
+
+fn part_directive(part_path: &str) -> String {
+    format!("part {};", dart_string_literal(part_path))
+}
+
+/// Check that a file with annotated classes contains the part
+/// directive of its generated code file. Directives of other generated
+/// code files ending with `.freezed.dart` are reported as misspelt.
+pub fn check_part_directive(file: &DartFile) -> Option<PartDirectiveProblem> {
+    let path = &file.path;
+    let parsed_file = &file.parsed_file;
+    let first_class = parsed_file.freezed_annotated_classes().next()?;
+    let expected_path = generated_code_file_name(path)?;
+    let expected = part_directive(&expected_path);
+
+    let parts = parsed_file.items.iter().filter_map(|item| match item {
+        TopLevelItems::Part(part) => Some(part),
+        _ => None,
+    });
+
+    let mut misspelt = None;
+    for part in parts {
+        if part.path == expected_path {
+            return None;
+        }
+        if misspelt.is_none() && part.path.ends_with(GENERATED_CODE_FILE_SUFFIX) {
+            misspelt = Some(part);
+        }
+    }
+
+    if let Some(part) = misspelt {
+        let found = part_directive(&part.path);
+        return Some(PartDirectiveProblem {
+            message: format!(
+                "Part directive `{}` does not match the generated code file, expected `{}`",
+                found, expected
+            ),
+            position: part.position,
+            fix_title: format!("Replace with `{}`", expected),
+            fix: SourceEdit {
+                start: part.position,
+                end: part.end_position,
+                text: expected,
+            },
+        });
+    }
+
+    Some(PartDirectiveProblem {
+        message: format!("Missing part directive `{}`", expected),
+        position: first_class.name_position,
+        fix_title: format!("Add `{}`", expected),
+        fix: insert_part_directive(file, expected),
+    })
+}
+
+/// Insert after the last library, import, export or part directive or
+/// at the start of the file. Line endings follow the file.
+fn insert_part_directive(file: &DartFile, directive: String) -> SourceEdit {
+    let mut edit = part_directive_edit(file, directive);
+    edit.text = LineEnding::Auto.apply(&file.contents, &edit.text);
+    edit
+}
+
+fn part_directive_edit(file: &DartFile, directive: String) -> SourceEdit {
+    let last_directive = file
+        .parsed_file
+        .items
+        .iter()
+        .rev()
+        .find_map(|item| match item {
+            TopLevelItems::Library(library) => Some((library.end_position, false)),
+            TopLevelItems::Import(import) => Some((import.end_position, false)),
+            TopLevelItems::Export(export) => Some((export.end_position, false)),
+            TopLevelItems::Part(part) => Some((part.end_position, true)),
+            TopLevelItems::PartOf(_) | TopLevelItems::Class(_) | TopLevelItems::Enum(_) => None,
+        });
+
+    match last_directive {
+        // Part directives are kept together
+        Some((position, true)) => SourceEdit {
+            start: position,
+            end: position,
+            text: format!("\n{}", directive),
+        },
+        Some((position, false)) => SourceEdit {
+            start: position,
+            end: position,
+            text: format!("\n\n{}", directive),
+        },
+        None => {
+            let start = SourcePosition::from_remaining_input(&file.contents);
+            SourceEdit {
+                start,
+                end: start,
+                text: format!("{}\n\n", directive),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn file(source: &str) -> DartFile {
+        DartFile::parse(Path::new("lib/user.dart"), source).unwrap()
+    }
+
+    fn fixed(source: &str) -> Option<String> {
+        check_part_directive(&file(source)).map(|problem| problem.fix.apply(source))
+    }
+
+    #[test]
+    fn part_directive_exists() {
+        assert_eq!(
+            fixed("part 'user.freezed.dart';\n@freezed\nclass User {}"),
+            None
+        );
+    }
+
+    #[test]
+    fn file_without_annotated_classes() {
+        assert_eq!(fixed("class User {}"), None);
+    }
+
+    #[test]
+    fn missing_part_directive_is_added_after_last_import() {
+        assert_eq!(
+            fixed("import 'a.dart';\nimport 'b.dart';\n\n@freezed\nclass User {}").unwrap(),
+            "import 'a.dart';\nimport 'b.dart';\n\npart 'user.freezed.dart';\n\n@freezed\nclass User {}"
+        );
+    }
+
+    #[test]
+    fn missing_part_directive_is_added_after_other_parts() {
+        assert_eq!(
+            fixed("import 'a.dart';\n\npart 'user.g.dart';\n\n@freezed\nclass User {}").unwrap(),
+            "import 'a.dart';\n\npart 'user.g.dart';\npart 'user.freezed.dart';\n\n@freezed\nclass User {}"
+        );
+    }
+
+    #[test]
+    fn missing_part_directive_is_added_to_start_of_file() {
+        assert_eq!(
+            fixed("@freezed\nclass User {}").unwrap(),
+            "part 'user.freezed.dart';\n\n@freezed\nclass User {}"
+        );
+    }
+
+    #[test]
+    fn missing_part_directive_follows_line_endings() {
+        assert_eq!(
+            fixed("import 'a.dart';\r\n\r\n@freezed\r\nclass User {}").unwrap(),
+            "import 'a.dart';\r\n\r\npart 'user.freezed.dart';\r\n\r\n@freezed\r\nclass User {}"
+        );
+    }
+
+    #[test]
+    fn misspelt_part_directive_is_replaced() {
+        let source = "part \"usr.freezed.dart\";\n@freezed\nclass User {}";
+        let problem = check_part_directive(&file(source)).unwrap();
+
+        assert_eq!(
+            problem.message,
+            "Part directive `part 'usr.freezed.dart';` does not match the generated code file, \
+            expected `part 'user.freezed.dart';`"
+        );
+        assert_eq!(
+            problem.fix.apply(source),
+            "part 'user.freezed.dart';\n@freezed\nclass User {}"
+        );
+    }
+}
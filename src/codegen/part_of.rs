@@ -1,6 +1,6 @@
 use anyhow::{anyhow, Result};
 
-use crate::file_finder::DartFile;
+use crate::{codegen::utils::dart_string_literal, file_finder::DartFile};
 
 pub fn generate_part_of_statement(file: &DartFile) -> Result<String> {
     let file_name = file
@@ -10,13 +10,5 @@ pub fn generate_part_of_statement(file: &DartFile) -> Result<String> {
         .to_str()
         .ok_or(anyhow!("Could not convert file name to string"))?;
 
-    if file_name.contains(['\'', '$']) {
-        // Prevent code injection and string formatting using file name
-        return Err(anyhow!(
-            "File name '{}' contains invalid characters",
-            file_name
-        ));
-    }
-
-    Ok(format!("part of '{}';", file_name,))
+    Ok(format!("part of {};", dart_string_literal(file_name)))
 }
@@ -1,3 +1,5 @@
+use crate::parser::{Annotation, Identifier, Parameter, ParameterKind};
+
 pub fn indent_lines(indentation: &str, input: impl AsRef<str>) -> String {
     input
         .as_ref()
@@ -12,3 +14,136 @@ pub fn indent_lines(indentation: &str, input: impl AsRef<str>) -> String {
         .collect::<Vec<String>>()
         .join("\n")
 }
+
+/// Escape text for a single quoted Dart string literal, so that
+/// quotes, backslashes and `$` do not end the string or start
+/// string interpolation.
+pub fn escape_dart_string(text: &str) -> String {
+    let mut escaped = String::new();
+    for c in text.chars() {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            '\'' => escaped.push_str("\\'"),
+            '$' => escaped.push_str("\\$"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c if c.is_control() => escaped.push_str(&format!("\\u{{{:x}}}", c as u32)),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Single quoted Dart string literal containing `text`
+pub fn dart_string_literal(text: &str) -> String {
+    format!("'{}'", escape_dart_string(text))
+}
+
+/// String interpolation like `$a` or `${this.a}` for an expression
+pub fn dart_string_interpolation(expression: &str) -> String {
+    let identifier = expression.chars().all(|c| c.is_alphanumeric() || c == '_');
+    if identifier {
+        format!("${}", expression)
+    } else {
+        format!("${{{}}}", expression)
+    }
+}
+
+/// Reference to field `name` from code which declares local variables
+/// or parameters `locals`. Fields shadowed by locals are referenced
+/// with `this.`.
+pub fn field_reference(name: &Identifier, locals: &[&str]) -> String {
+    if locals.contains(&name.as_str()) {
+        format!("this.{}", name)
+    } else {
+        name.to_string()
+    }
+}
+
+/// Generate doc comment and annotations for a declaration.
+/// Every line ends with a newline.
+pub fn generate_metadata<'a>(
+    doc_comment: &[String],
+    annotations: impl IntoIterator<Item = &'a Annotation>,
+) -> String {
+    doc_comment
+        .iter()
+        .cloned()
+        .chain(annotations.into_iter().map(|a| a.to_string()))
+        .map(|line| format!("{}\n", line))
+        .collect()
+}
+
+/// Generate parameter list like `(\n  int a, [\n  int? b,\n])`.
+///
+/// Parameter code is generated with `param_code` and the list
+/// is formatted like `dart format` formats lists with trailing comma.
+pub fn generate_parameter_list(
+    params: &[Parameter],
+    param_code: impl Fn(&Parameter) -> String,
+) -> String {
+    let mut positional = params
+        .iter()
+        .filter(|param| param.kind == ParameterKind::Positional)
+        .map(|param| format!("{},", param_code(param)))
+        .collect::<Vec<String>>();
+
+    let optional = params
+        .iter()
+        .filter(|param| param.kind != ParameterKind::Positional)
+        .collect::<Vec<&Parameter>>();
+
+    let delimiters = optional.first().map(|param| {
+        if param.kind == ParameterKind::OptionalPositional {
+            ("[", "]")
+        } else {
+            ("{", "}")
+        }
+    });
+
+    let optional = optional
+        .iter()
+        .map(|param| format!("{},", param_code(param)))
+        .collect::<Vec<String>>();
+
+    match delimiters {
+        None if positional.is_empty() => "()".to_string(),
+        None => format!("(\n{}\n)", indent_lines("  ", positional.join("\n"))),
+        Some((start, end)) if positional.is_empty() => format!(
+            "({}\n{}\n{})",
+            start,
+            indent_lines("  ", optional.join("\n")),
+            end,
+        ),
+        Some((start, end)) => {
+            if let Some(last) = positional.last_mut() {
+                last.push(' ');
+                last.push_str(start);
+            }
+            positional.extend(optional);
+            format!("(\n{}\n{})", indent_lines("  ", positional.join("\n")), end)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn escape_dart_string_escapes_special_characters() {
+        assert_eq!(escape_dart_string("a $b ${c}"), "a \\$b \\${c}");
+        assert_eq!(escape_dart_string("it's \"a\""), "it\\'s \"a\"");
+        assert_eq!(escape_dart_string("a\\b"), "a\\\\b");
+        assert_eq!(escape_dart_string("a\nb\r\n\tc"), "a\\nb\\r\\n\\tc");
+        assert_eq!(escape_dart_string("a\u{7}b"), "a\\u{7}b");
+        assert_eq!(escape_dart_string("ä"), "ä");
+    }
+
+    #[test]
+    fn dart_string_literal_is_single_quoted() {
+        assert_eq!(dart_string_literal(""), "''");
+        assert_eq!(dart_string_literal("A's $a\n"), "'A\\'s \\$a\\n'");
+    }
+}
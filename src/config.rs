@@ -1,21 +1,109 @@
 use std::path::PathBuf;
 
-use clap::Parser;
+use clap::{Parser, Subcommand};
 
-#[derive(Parser)]
-#[command(author, version, about)]
+use crate::message_format::MessageFormat;
+use crate::source_text::LineEnding;
+
+#[derive(Parser, Clone)]
+#[command(
+    author,
+    version = env!("ICEGEN_BUILD_VERSION"),
+    about,
+    subcommand_negates_reqs = true
+)]
 pub struct ArgsConfig {
+    #[command(subcommand)]
+    pub command: Option<Command>,
+
     /// Directory where Dart code files are located. If '.dart' files
     /// containing @freezed or @Freezed annotated classes are found
     /// from this directory or its subdirectories, files ending
     /// with '.freezed.dart' will be generated next to found files.
-    #[arg(long, value_name = "DIR")]
-    pub code_dir: PathBuf,
+    /// Runs of the same user in the same directory or in its parent
+    /// and child directories wait for each other using lock files in
+    /// the runtime directory of the user or in the temporary directory
+    /// of the system.
+    #[arg(long, value_name = "DIR", required_unless_present = "stdin")]
+    pub code_dir: Option<PathBuf>,
+
+    /// Read one Dart file from standard input and write the generated
+    /// code to standard output instead of scanning a directory. Nothing
+    /// is written if the file does not contain annotated classes.
+    /// Errors are written to standard error as
+    /// 'FILE:LINE:COLUMN: error: MESSAGE' lines.
+    #[arg(long, conflicts_with = "code_dir", requires = "file_name")]
+    pub stdin: bool,
+
+    /// Path of the Dart file read from standard input. The file name is
+    /// used in the 'part of' statement and exports are followed
+    /// relative to the path.
+    #[arg(
+        long,
+        value_name = "FILE",
+        requires = "stdin",
+        conflicts_with = "code_dir"
+    )]
+    pub file_name: Option<PathBuf>,
 
     /// Force update of an existing generated file even if it contains
     /// unknown file header.
     #[arg(long)]
     pub force: bool,
+
+    /// Fix problems in Dart files which can be fixed automatically.
+    /// A missing 'part' directive of the generated code file is added
+    /// after the last import and a misspelt one is replaced.
+    #[arg(long, conflicts_with = "stdin")]
+    pub fix: bool,
+
+    /// Check that generated code files are up to date without writing
+    /// them. A file is stale if it is missing or if the icegen package
+    /// version or the hash of the Dart file in its header differs. The
+    /// build after '+' in the version is ignored. Changes to other files
+    /// and options are not detected.
+    #[arg(long, conflicts_with_all = ["stdin", "fix", "force"])]
+    pub check: bool,
+
+    /// Stop at the first file which can not be parsed or generated.
+    /// By default other files are still handled and the failed files
+    /// are listed at the end.
+    #[arg(long)]
+    pub fail_fast: bool,
+
+    /// Library which exports DiagnosticableTreeMixin from
+    /// 'package:flutter/foundation.dart' in addition to the built-in
+    /// Flutter libraries. Can be used multiple times.
+    #[arg(long, value_name = "URI")]
+    pub diagnosticable_library: Vec<String>,
+
+    /// Follow export directives of project-local libraries when detecting
+    /// if DiagnosticableTreeMixin is imported. Relative imports and
+    /// 'package:' imports of the package containing the file are followed.
+    #[arg(long)]
+    pub follow_exports: bool,
+
+    /// Output format of errors. With 'json', events like parsed files,
+    /// generated classes, written files and errors are written as JSON
+    /// objects, one per line, to standard output or to standard error
+    /// if --stdin is used.
+    #[arg(long, value_enum, value_name = "FORMAT", default_value_t = MessageFormat::Human)]
+    pub message_format: MessageFormat,
+
+    /// Line ending of generated code files. With 'auto' the line ending
+    /// of the first line of the Dart file is used.
+    #[arg(long, value_enum, value_name = "LINE_ENDING", default_value_t = LineEnding::Auto)]
+    pub line_ending: LineEnding,
+}
+
+#[derive(Subcommand, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Command {
+    /// Start a Language Server Protocol server
+    ///
+    /// The server communicates over standard input and output. It reports
+    /// errors of files containing annotated classes, offers code actions
+    /// to fix them and updates generated code files when files are saved.
+    Lsp,
 }
 
 pub fn get_config() -> ArgsConfig {
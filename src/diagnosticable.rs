@@ -0,0 +1,345 @@
+//! Detect if Flutter Diagnosticable support is imported
+
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::config::ArgsConfig;
+use crate::parser::{ImportStatement, ParsedFile, TopLevelItems};
+
+/// Libraries which export `DiagnosticableTreeMixin` and other
+/// Diagnosticable related types from `package:flutter/foundation.dart`.
+pub const DEFAULT_DIAGNOSTICABLE_LIBRARIES: &[&str] = &[
+    "package:flutter/foundation.dart",
+    "package:flutter/widgets.dart",
+    "package:flutter/material.dart",
+    "package:flutter/cupertino.dart",
+];
+
+/// Libraries which export `ColorProperty` from
+/// `package:flutter/painting.dart`.
+pub const COLOR_PROPERTY_LIBRARIES: &[&str] = &[
+    "package:flutter/painting.dart",
+    "package:flutter/widgets.dart",
+    "package:flutter/material.dart",
+    "package:flutter/cupertino.dart",
+];
+
+pub const COLOR_PROPERTY_NAME: &str = "ColorProperty";
+
+const PACKAGE_URI_SCHEME: &str = "package:";
+const PUBSPEC_FILE_NAME: &str = "pubspec.yaml";
+const PUBSPEC_NAME_KEY: &str = "name:";
+const PACKAGE_LIB_DIR: &str = "lib";
+
+pub struct DiagnosticableLibraries {
+    libraries: Vec<String>,
+    color_property_libraries: Vec<String>,
+    follow_exports: bool,
+}
+
+impl DiagnosticableLibraries {
+    pub fn new(additional_libraries: &[String], follow_exports: bool) -> Self {
+        let mut libraries: Vec<String> = DEFAULT_DIAGNOSTICABLE_LIBRARIES
+            .iter()
+            .map(|library| library.to_string())
+            .collect();
+        libraries.extend(additional_libraries.iter().cloned());
+
+        let color_property_libraries = COLOR_PROPERTY_LIBRARIES
+            .iter()
+            .map(|library| library.to_string())
+            .collect();
+
+        Self {
+            libraries,
+            color_property_libraries,
+            follow_exports,
+        }
+    }
+
+    pub fn from_config(config: &ArgsConfig) -> Self {
+        Self::new(&config.diagnosticable_library, config.follow_exports)
+    }
+
+    /// Returns true if `import` located in file `importing_file` makes
+    /// `name` from `package:flutter/foundation.dart` visible without prefix.
+    /// If `name` is [COLOR_PROPERTY_NAME], then libraries exporting
+    /// `package:flutter/painting.dart` are checked instead.
+    pub fn import_makes_visible(
+        &self,
+        importing_file: &Path,
+        import: &ImportStatement,
+        name: &str,
+    ) -> bool {
+        if !import.makes_visible_without_prefix(name) {
+            return false;
+        }
+
+        self.library_exports(importing_file, &import.path, name, &mut HashSet::new())
+    }
+
+    fn library_exports(
+        &self,
+        current_file: &Path,
+        uri: &str,
+        name: &str,
+        visited: &mut HashSet<PathBuf>,
+    ) -> bool {
+        let libraries = if name == COLOR_PROPERTY_NAME {
+            &self.color_property_libraries
+        } else {
+            &self.libraries
+        };
+
+        if libraries.iter().any(|library| library == uri) {
+            return true;
+        }
+
+        if !self.follow_exports {
+            return false;
+        }
+
+        let Some(library_file) = resolve_project_local_uri(current_file, uri) else {
+            return false;
+        };
+
+        if !visited.insert(library_file.clone()) {
+            return false;
+        }
+
+        let Ok(contents) = fs::read_to_string(&library_file) else {
+            return false;
+        };
+
+        let Ok(parsed_file) = ParsedFile::parse_dart_file(&contents) else {
+            return false;
+        };
+
+        parsed_file.items.iter().any(|item| {
+            if let TopLevelItems::Export(export) = item {
+                export.exports(name)
+                    && self.library_exports(&library_file, &export.path, name, visited)
+            } else {
+                false
+            }
+        })
+    }
+}
+
+/// Resolve relative URIs and `package:` URIs of the package containing
+/// `current_file` to file paths.
+fn resolve_project_local_uri(current_file: &Path, uri: &str) -> Option<PathBuf> {
+    if let Some(package_path) = uri.strip_prefix(PACKAGE_URI_SCHEME) {
+        let (package_name, path_in_lib) = package_path.split_once('/')?;
+        let package_dir = find_package_dir(current_file)?;
+        if read_package_name(&package_dir)? != package_name {
+            return None;
+        }
+        Some(package_dir.join(PACKAGE_LIB_DIR).join(path_in_lib))
+    } else if uri.contains(':') {
+        // Other schemes like 'dart:' are not project-local
+        None
+    } else {
+        Some(current_file.parent()?.join(uri))
+    }
+}
+
+fn find_package_dir(current_file: &Path) -> Option<PathBuf> {
+    current_file
+        .ancestors()
+        .skip(1)
+        .find(|dir| dir.join(PUBSPEC_FILE_NAME).is_file())
+        .map(|dir| dir.to_owned())
+}
+
+fn read_package_name(package_dir: &Path) -> Option<String> {
+    let pubspec = fs::read_to_string(package_dir.join(PUBSPEC_FILE_NAME)).ok()?;
+    pubspec.lines().find_map(|line| {
+        line.strip_prefix(PUBSPEC_NAME_KEY)
+            .map(|name| name.trim().trim_matches(['\'', '"']).to_string())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_utils::TempDir;
+
+    use super::*;
+
+    const MIXIN: &str = "DiagnosticableTreeMixin";
+
+    /// Project of package `app` with `lib/a.dart` importing libraries
+    fn project() -> TempDir {
+        let dir = TempDir::new();
+        dir.write("pubspec.yaml", "name: 'app'\nversion: 1.0.0\n");
+        dir
+    }
+
+    fn import_makes_visible(
+        libraries: &DiagnosticableLibraries,
+        importing_file: &Path,
+        import: &str,
+        name: &str,
+    ) -> bool {
+        let parsed_file = ParsedFile::parse_dart_file(import).unwrap();
+        match &parsed_file.items[..] {
+            [TopLevelItems::Import(import)] => {
+                libraries.import_makes_visible(importing_file, import, name)
+            }
+            _ => panic!("import not parsed"),
+        }
+    }
+
+    #[test]
+    fn default_library_is_detected_without_following_exports() {
+        let libraries = DiagnosticableLibraries::new(&[], false);
+        let file = Path::new("lib/a.dart");
+        assert!(import_makes_visible(
+            &libraries,
+            file,
+            "import 'package:flutter/material.dart';",
+            MIXIN
+        ));
+        assert!(!import_makes_visible(
+            &libraries,
+            file,
+            "import 'package:flutter/material.dart' hide DiagnosticableTreeMixin;",
+            MIXIN
+        ));
+        assert!(!import_makes_visible(
+            &libraries,
+            file,
+            "import 'package:flutter/foundation.dart';",
+            COLOR_PROPERTY_NAME
+        ));
+    }
+
+    #[test]
+    fn re_export_of_flutter_library_is_followed() {
+        let dir = project();
+        let file = dir.write("lib/a.dart", "");
+        dir.write("lib/ui.dart", "export 'package:flutter/material.dart';");
+        let import = "import 'ui.dart';";
+
+        let libraries = DiagnosticableLibraries::new(&[], true);
+        assert!(import_makes_visible(&libraries, &file, import, MIXIN));
+        assert!(import_makes_visible(
+            &libraries,
+            &file,
+            import,
+            COLOR_PROPERTY_NAME
+        ));
+
+        let libraries = DiagnosticableLibraries::new(&[], false);
+        assert!(!import_makes_visible(&libraries, &file, import, MIXIN));
+    }
+
+    #[test]
+    fn package_import_of_own_package_is_followed() {
+        let dir = project();
+        let file = dir.write("lib/models/a.dart", "");
+        dir.write(
+            "lib/src/ui.dart",
+            "export 'package:flutter/foundation.dart';",
+        );
+        let libraries = DiagnosticableLibraries::new(&[], true);
+
+        assert!(import_makes_visible(
+            &libraries,
+            &file,
+            "import 'package:app/src/ui.dart';",
+            MIXIN
+        ));
+        assert!(!import_makes_visible(
+            &libraries,
+            &file,
+            "import 'package:other/src/ui.dart';",
+            MIXIN
+        ));
+    }
+
+    #[test]
+    fn export_combinators_are_applied() {
+        let dir = project();
+        let file = dir.write("lib/a.dart", "");
+        dir.write(
+            "lib/hidden.dart",
+            "export 'package:flutter/foundation.dart' hide DiagnosticableTreeMixin;",
+        );
+        dir.write(
+            "lib/shown.dart",
+            "export 'package:flutter/foundation.dart' show DiagnosticableTreeMixin;",
+        );
+        let libraries = DiagnosticableLibraries::new(&[], true);
+
+        assert!(!import_makes_visible(
+            &libraries,
+            &file,
+            "import 'hidden.dart';",
+            MIXIN
+        ));
+        assert!(import_makes_visible(
+            &libraries,
+            &file,
+            "import 'hidden.dart';",
+            "DiagnosticLevel"
+        ));
+        assert!(import_makes_visible(
+            &libraries,
+            &file,
+            "import 'shown.dart';",
+            MIXIN
+        ));
+        assert!(!import_makes_visible(
+            &libraries,
+            &file,
+            "import 'shown.dart';",
+            "DiagnosticLevel"
+        ));
+    }
+
+    #[test]
+    fn export_cycle_terminates() {
+        let dir = project();
+        let file = dir.write("lib/a.dart", "");
+        dir.write("lib/b.dart", "export 'c.dart';");
+        dir.write("lib/c.dart", "export 'b.dart';");
+        dir.write(
+            "lib/d.dart",
+            "export 'e.dart';\nexport 'package:flutter/widgets.dart';",
+        );
+        dir.write("lib/e.dart", "export 'd.dart';");
+        let libraries = DiagnosticableLibraries::new(&[], true);
+
+        assert!(!import_makes_visible(
+            &libraries,
+            &file,
+            "import 'b.dart';",
+            MIXIN
+        ));
+        assert!(import_makes_visible(
+            &libraries,
+            &file,
+            "import 'e.dart';",
+            MIXIN
+        ));
+    }
+
+    #[test]
+    fn package_name_is_read_from_nearest_pubspec() {
+        let dir = project();
+        dir.write("tool/pubspec.yaml", "name: \"tool\"\n");
+        let file = dir.write("tool/lib/a.dart", "");
+
+        let package_dir = find_package_dir(&file).unwrap();
+        assert_eq!(package_dir, dir.path().join("tool"));
+        assert_eq!(read_package_name(&package_dir).unwrap(), "tool");
+        assert_eq!(
+            resolve_project_local_uri(&file, "package:tool/b.dart").unwrap(),
+            dir.path().join("tool/lib/b.dart")
+        );
+        assert_eq!(resolve_project_local_uri(&file, "package:app/b.dart"), None);
+        assert_eq!(resolve_project_local_uri(&file, "dart:core"), None);
+    }
+}
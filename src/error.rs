@@ -0,0 +1,315 @@
+//! Errors of the public API
+
+use std::fmt;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::event::Event;
+
+/// One-based line and column in a source file
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Location {
+    pub line: usize,
+    pub column: usize,
+}
+
+const INVALID_UTF8_MESSAGE: &str = "File is not valid UTF-8. Save it with UTF-8 encoding.";
+
+#[derive(Debug)]
+pub enum Error {
+    /// Reading or writing a file or a directory failed
+    Io { path: PathBuf, source: io::Error },
+    /// Dart file is not valid UTF-8. Location is the first invalid byte.
+    InvalidUtf8 { path: PathBuf, location: Location },
+    /// Dart code could not be parsed. Location is where parsing
+    /// failed if it is known.
+    Parse {
+        path: Option<PathBuf>,
+        location: Option<Location>,
+        message: String,
+    },
+    /// Code could not be generated, for example because a class
+    /// uses an unsupported feature
+    Generate {
+        path: Option<PathBuf>,
+        location: Option<Location>,
+        message: String,
+    },
+    /// Dart file does not include the generated code file with
+    /// `part 'x.freezed.dart';`
+    PartDirective {
+        path: PathBuf,
+        location: Location,
+        message: String,
+    },
+    /// Existing generated code file does not start with the expected
+    /// header, so it is not overwritten without `--force`
+    UnknownGeneratedFileHeader { path: PathBuf },
+    /// Generated code file is missing or was generated from another
+    /// version of the Dart file or by another icegen version
+    StaleGeneratedFile { path: PathBuf },
+    /// Configuration is not valid
+    InvalidConfig { message: String },
+}
+
+impl Error {
+    pub(crate) fn io(path: impl Into<PathBuf>, source: io::Error) -> Self {
+        Self::Io {
+            path: path.into(),
+            source,
+        }
+    }
+
+    /// Convert error from code generation. Location is available if
+    /// the error is a [LocatedError].
+    pub(crate) fn generate(path: Option<PathBuf>, error: anyhow::Error) -> Self {
+        match error.downcast::<LocatedError>() {
+            Ok(located) => Self::Generate {
+                path,
+                location: Some(located.location),
+                message: located.message,
+            },
+            Err(error) => Self::Generate {
+                path,
+                location: None,
+                message: error.to_string(),
+            },
+        }
+    }
+
+    /// Add path to errors which do not have a path yet
+    pub(crate) fn with_path(self, path: &Path) -> Self {
+        match self {
+            Self::Parse {
+                path: None,
+                location,
+                message,
+            } => Self::Parse {
+                path: Some(path.to_owned()),
+                location,
+                message,
+            },
+            Self::Generate {
+                path: None,
+                location,
+                message,
+            } => Self::Generate {
+                path: Some(path.to_owned()),
+                location,
+                message,
+            },
+            error => error,
+        }
+    }
+
+    /// Location of the error in the Dart file if known
+    pub fn location(&self) -> Option<Location> {
+        match self {
+            Self::Parse { location, .. } | Self::Generate { location, .. } => *location,
+            Self::InvalidUtf8 { location, .. } | Self::PartDirective { location, .. } => {
+                Some(*location)
+            }
+            _ => None,
+        }
+    }
+
+    /// Stable code of the error kind for machine-readable output
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::Io { .. } => "io",
+            Self::InvalidUtf8 { .. } => "invalid-utf8",
+            Self::Parse { .. } => "parse",
+            Self::Generate { .. } => "generate",
+            Self::PartDirective { .. } => "part-directive",
+            Self::UnknownGeneratedFileHeader { .. } => "unknown-generated-file-header",
+            Self::StaleGeneratedFile { .. } => "stale-generated-file",
+            Self::InvalidConfig { .. } => "invalid-config",
+        }
+    }
+
+    /// Error message without the path and the location
+    pub fn message(&self) -> String {
+        match self {
+            Self::Io { source, .. } => format!("Failed to access file: {}", source),
+            Self::InvalidUtf8 { .. } => INVALID_UTF8_MESSAGE.to_string(),
+            Self::Parse { message, .. } => format!("Failed to parse Dart code: {}", message),
+            Self::Generate { message, .. } | Self::PartDirective { message, .. } => message.clone(),
+            Self::UnknownGeneratedFileHeader { .. } => {
+                "Generated code file does not start with the expected header".to_string()
+            }
+            Self::StaleGeneratedFile { .. } => {
+                "Generated code file is not up to date. Run icegen without --check to update it."
+                    .to_string()
+            }
+            Self::InvalidConfig { message } => message.clone(),
+        }
+    }
+
+    /// Path of the file which caused the error
+    pub fn path(&self) -> Option<&PathBuf> {
+        match self {
+            Self::Io { path, .. }
+            | Self::InvalidUtf8 { path, .. }
+            | Self::PartDirective { path, .. }
+            | Self::UnknownGeneratedFileHeader { path }
+            | Self::StaleGeneratedFile { path } => Some(path),
+            Self::Parse { path, .. } | Self::Generate { path, .. } => path.as_ref(),
+            Self::InvalidConfig { .. } => None,
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io { path, source } => {
+                write!(f, "Failed to access {:?}: {}", path, source)
+            }
+            Self::InvalidUtf8 { path, location } => write!(
+                f,
+                "{}:{}:{}: {}",
+                path.display(),
+                location.line,
+                location.column,
+                INVALID_UTF8_MESSAGE
+            ),
+            Self::Parse {
+                path: Some(path),
+                location: Some(location),
+                message,
+            } => write!(
+                f,
+                "Failed to parse {:?} at {}:{}: {}",
+                path, location.line, location.column, message
+            ),
+            Self::Parse {
+                path: Some(path),
+                location: None,
+                message,
+            } => write!(f, "Failed to parse {:?}: {}", path, message),
+            Self::Parse {
+                path: None,
+                message,
+                ..
+            } => write!(f, "Failed to parse Dart code: {}", message),
+            Self::Generate {
+                path,
+                location,
+                message,
+            } => {
+                match (path, location) {
+                    (Some(path), Some(location)) => write!(
+                        f,
+                        "{}:{}:{}: ",
+                        path.display(),
+                        location.line,
+                        location.column
+                    )?,
+                    (Some(path), None) => write!(f, "{}: ", path.display())?,
+                    (None, Some(location)) => write!(f, "{}:{}: ", location.line, location.column)?,
+                    (None, None) => (),
+                }
+                write!(f, "{}", message)
+            }
+            Self::PartDirective {
+                path,
+                location,
+                message,
+            } => write!(
+                f,
+                "{}:{}:{}: {}",
+                path.display(),
+                location.line,
+                location.column,
+                message
+            ),
+            Self::UnknownGeneratedFileHeader { path } => write!(
+                f,
+                "Generated code file {:?} does not start with the expected header",
+                path
+            ),
+            Self::StaleGeneratedFile { path } => {
+                write!(
+                    f,
+                    "Generated code file {:?} is not up to date. Run icegen without --check to update it.",
+                    path
+                )
+            }
+            Self::InvalidConfig { message } => write!(f, "{}", message),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+/// Code generation error located in the Dart file
+#[derive(Debug)]
+pub(crate) struct LocatedError {
+    pub location: Location,
+    pub message: String,
+}
+
+impl fmt::Display for LocatedError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+impl std::error::Error for LocatedError {}
+
+impl LocatedError {
+    /// Locate `error` at `location` unless it is already located
+    pub fn locate(error: anyhow::Error, location: Location) -> anyhow::Error {
+        if error.is::<LocatedError>() {
+            error
+        } else {
+            LocatedError {
+                location,
+                message: error.to_string(),
+            }
+            .into()
+        }
+    }
+}
+
+/// Errors of files which failed. Other files are still handled
+/// unless `fail_fast` is set.
+#[derive(Debug, Default)]
+pub(crate) struct ErrorCollector {
+    pub fail_fast: bool,
+    pub errors: Vec<Error>,
+}
+
+impl ErrorCollector {
+    pub fn new(fail_fast: bool) -> Self {
+        Self {
+            fail_fast,
+            errors: vec![],
+        }
+    }
+
+    /// Returns the error if `fail_fast` is set. Otherwise the error is
+    /// reported as [Event::Diagnostic], stored and `None` is returned.
+    pub fn collect<T>(
+        &mut self,
+        result: Result<T, Error>,
+        on_event: &mut dyn FnMut(Event),
+    ) -> Result<Option<T>, Error> {
+        match result {
+            Ok(value) => Ok(Some(value)),
+            Err(error) if self.fail_fast => Err(error),
+            Err(error) => {
+                on_event(Event::diagnostic(&error));
+                self.errors.push(error);
+                Ok(None)
+            }
+        }
+    }
+}
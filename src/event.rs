@@ -0,0 +1,44 @@
+//! Events reported while generating code
+
+use std::path::PathBuf;
+
+use crate::error::{Error, Location};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Event {
+    /// Dart file was read and parsed
+    FileParsed { path: PathBuf },
+    /// Code was generated for class `class_name` in Dart file `path`
+    ClassGenerated { path: PathBuf, class_name: String },
+    /// Generated code file `path` for Dart file `source_path` was written
+    FileWritten { path: PathBuf, source_path: PathBuf },
+    /// Generated code file `path` for Dart file `source_path` was
+    /// already up to date
+    FileUnchanged { path: PathBuf, source_path: PathBuf },
+    /// Dart file `path` was modified to fix a problem
+    FileFixed { path: PathBuf, fix: String },
+    /// No code was generated for Dart file `path`
+    FileSkipped { path: PathBuf, reason: String },
+    /// Dart file or directory `path` could not be handled
+    FileFailed { path: PathBuf },
+    /// Error of a file which failed. It is reported when the error
+    /// occurs, so it follows the other events of the file.
+    Diagnostic {
+        /// Stable code of the error kind like [Error::code]
+        code: &'static str,
+        path: Option<PathBuf>,
+        location: Option<Location>,
+        message: String,
+    },
+}
+
+impl Event {
+    pub fn diagnostic(error: &Error) -> Self {
+        Self::Diagnostic {
+            code: error.code(),
+            path: error.path().cloned(),
+            location: error.location(),
+            message: error.message(),
+        }
+    }
+}
@@ -1,11 +1,13 @@
 //! Find and parse Dart files from a directory
 
-use anyhow::{Context, Result};
 use std::fs;
 use std::path::Path;
 use std::path::PathBuf;
 
+use crate::error::{Error, ErrorCollector};
+use crate::event::Event;
 use crate::parser::ParsedFile;
+use crate::source_text;
 
 const DART_FILE_EXTENSION: &str = ".dart";
 const FREEZED_GENERATED_CODE_FILE_EXTENSION: &str = ".freezed.dart";
@@ -14,9 +16,38 @@ pub const FREEZED_GENERATED_CODE_FILE_EXTENSION_WITHOUT_LEADING_DOT: &str = "fre
 #[derive(Debug)]
 pub struct DartFile {
     pub path: PathBuf,
+    /// Contents without the byte order mark
+    pub contents: String,
+    pub has_byte_order_mark: bool,
     pub parsed_file: ParsedFile,
 }
 
+impl DartFile {
+    /// Parse contents `source` of Dart file `path`. A byte order mark is
+    /// removed before parsing.
+    pub fn parse(path: &Path, source: &str) -> Result<Self, Error> {
+        let (contents, has_byte_order_mark) = source_text::strip_byte_order_mark(source);
+        let parsed_file = ParsedFile::parse_dart_file(contents).map_err(|e| e.with_path(path))?;
+
+        Ok(Self {
+            path: path.to_owned(),
+            contents: contents.to_string(),
+            has_byte_order_mark,
+            parsed_file,
+        })
+    }
+
+    /// Contents including the byte order mark if the file has one
+    pub fn encoded_contents(&self) -> String {
+        source_text::with_byte_order_mark(&self.contents, self.has_byte_order_mark)
+    }
+}
+
+/// Path of the generated code file for Dart file `path`
+pub fn generated_code_path(path: &Path) -> PathBuf {
+    path.with_extension(FREEZED_GENERATED_CODE_FILE_EXTENSION_WITHOUT_LEADING_DOT)
+}
+
 /// Parsed Dart files in a directory and its subdirectories.
 ///
 /// This does not contain Dart files ending with `.freezed.dart`.
@@ -27,10 +58,12 @@ pub struct ParsedDartFiles {
 
 pub fn parse_freezed_classes_from_dart_files(
     code_dir: impl AsRef<Path>,
-) -> Result<ParsedDartFiles> {
+    errors: &mut ErrorCollector,
+    on_event: &mut dyn FnMut(Event),
+) -> Result<ParsedDartFiles, Error> {
     let mut parsed_files = Vec::<DartFile>::new();
 
-    handle_one_code_dir(code_dir, &mut parsed_files)?;
+    handle_one_code_dir(code_dir, &mut parsed_files, errors, on_event)?;
 
     Ok(ParsedDartFiles {
         code_files: parsed_files,
@@ -40,12 +73,23 @@ pub fn parse_freezed_classes_from_dart_files(
 fn handle_one_code_dir(
     code_dir: impl AsRef<Path>,
     parsing_results: &mut Vec<DartFile>,
-) -> Result<()> {
-    for entry in fs::read_dir(code_dir)? {
-        let entry = entry?;
+    errors: &mut ErrorCollector,
+    on_event: &mut dyn FnMut(Event),
+) -> Result<(), Error> {
+    let code_dir = code_dir.as_ref();
+    let read_dir = fs::read_dir(code_dir).map_err(|e| Error::io(code_dir, e));
+    let Some(entries) = collect_failure(code_dir, read_dir, errors, on_event)? else {
+        return Ok(());
+    };
+
+    for entry in entries {
+        let entry = entry.map_err(|e| Error::io(code_dir, e));
+        let Some(entry) = collect_failure(code_dir, entry, errors, on_event)? else {
+            continue;
+        };
 
         if entry.path().is_dir() {
-            handle_one_code_dir(entry.path(), parsing_results)?;
+            handle_one_code_dir(entry.path(), parsing_results, errors, on_event)?;
             continue;
         }
 
@@ -64,7 +108,13 @@ fn handle_one_code_dir(
             continue;
         }
 
-        if let Some(parsed_classes) = handle_dart_file(entry.path())? {
+        let parsed_classes = handle_dart_file(entry.path());
+        if let Some(Some(parsed_classes)) =
+            collect_failure(&entry.path(), parsed_classes, errors, on_event)?
+        {
+            on_event(Event::FileParsed {
+                path: parsed_classes.path.clone(),
+            });
             parsing_results.push(parsed_classes);
         }
     }
@@ -72,15 +122,26 @@ fn handle_one_code_dir(
     Ok(())
 }
 
-fn handle_dart_file(dart_code_file: impl AsRef<Path>) -> Result<Option<DartFile>> {
-    let path = dart_code_file.as_ref();
-    let contents = fs::read_to_string(path)
-        .with_context(|| format!("Failed to read Dart code file at {:?}", path))?;
+/// Report failure of the file or the directory `path` and collect the error
+fn collect_failure<T>(
+    path: &Path,
+    result: Result<T, Error>,
+    errors: &mut ErrorCollector,
+    on_event: &mut dyn FnMut(Event),
+) -> Result<Option<T>, Error> {
+    if result.is_err() {
+        on_event(Event::FileFailed {
+            path: path.to_owned(),
+        });
+    }
 
-    let file = ParsedFile::parse_dart_file(&contents)?;
+    errors.collect(result, on_event)
+}
+
+fn handle_dart_file(dart_code_file: impl AsRef<Path>) -> Result<Option<DartFile>, Error> {
+    let path = dart_code_file.as_ref();
+    let bytes = fs::read(path).map_err(|e| Error::io(path, e))?;
+    let source = source_text::decode(path, bytes)?;
 
-    Ok(Some(DartFile {
-        path: path.to_owned(),
-        parsed_file: file,
-    }))
+    DartFile::parse(path, &source).map(Some)
 }
@@ -1,55 +1,407 @@
 //! Update generated code files if needed
 
-use anyhow::{anyhow, Result};
-use std::fs;
+use std::fs::{self, File};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::process;
+use std::sync::atomic::{AtomicUsize, Ordering};
 
-use crate::codegen::{generate_data_class_file, GENERATED_FILE_HEADER};
-use crate::config::ArgsConfig;
-use crate::file_finder::{
-    DartFile, ParsedDartFiles, FREEZED_GENERATED_CODE_FILE_EXTENSION_WITHOUT_LEADING_DOT,
+use crate::codegen::{
+    generate_data_class_file,
+    header::{self, Header},
+    part_directive,
 };
+use crate::config::ArgsConfig;
+use crate::diagnosticable::DiagnosticableLibraries;
+use crate::error::{Error, ErrorCollector};
+use crate::event::Event;
+use crate::file_finder::{generated_code_path, DartFile, ParsedDartFiles};
+use crate::source_text;
 
 pub fn update_generated_code_for_parsed_files(
     config: &ArgsConfig,
     files: ParsedDartFiles,
-) -> Result<()> {
+    errors: &mut ErrorCollector,
+    on_event: &mut dyn FnMut(Event),
+) -> Result<(), Error> {
+    let libraries = DiagnosticableLibraries::from_config(config);
+
     for file in files.code_files {
         if !file.parsed_file.contains_freezed_annotated_class() {
+            on_event(Event::FileSkipped {
+                path: file.path,
+                reason: NO_ANNOTATED_CLASSES.to_string(),
+            });
             continue;
         }
 
-        let generated_code = generate_data_class_file(&file)?;
-        update_generated_code_if_needed(config, file, generated_code)?;
+        let path = file.path.clone();
+        let result = check_part_directive(config, file, on_event).and_then(|file| {
+            generate_data_class_file(&file, &libraries)
+                .map_err(|e| Error::generate(Some(file.path.clone()), e))
+                .and_then(|generated_code| {
+                    report_generated_classes(&file, on_event);
+                    update_generated_code_if_needed(config, file, generated_code, on_event)
+                })
+        });
+
+        if result.is_err() {
+            on_event(Event::FileFailed { path });
+        }
+        errors.collect(result, on_event)?;
     }
 
     Ok(())
 }
 
+/// Check that generated code files are up to date without generating
+/// code. Only the icegen versions and the source hashes in the headers
+/// are compared.
+pub fn check_generated_code_for_parsed_files(
+    files: ParsedDartFiles,
+    errors: &mut ErrorCollector,
+    on_event: &mut dyn FnMut(Event),
+) -> Result<(), Error> {
+    for file in files.code_files {
+        if !file.parsed_file.contains_freezed_annotated_class() {
+            on_event(Event::FileSkipped {
+                path: file.path,
+                reason: NO_ANNOTATED_CLASSES.to_string(),
+            });
+            continue;
+        }
+
+        let generated_code_path = generated_code_path(&file.path);
+        let result =
+            read_generated_code(&generated_code_path).and_then(
+                |generated_code| match generated_code {
+                    Some(generated_code) if !header::is_stale(&file.contents, &generated_code) => {
+                        Ok(())
+                    }
+                    _ => Err(Error::StaleGeneratedFile {
+                        path: generated_code_path.clone(),
+                    }),
+                },
+            );
+
+        match result {
+            Ok(()) => on_event(Event::FileUnchanged {
+                path: generated_code_path,
+                source_path: file.path,
+            }),
+            Err(_) => on_event(Event::FileFailed { path: file.path }),
+        }
+        errors.collect(result, on_event)?;
+    }
+
+    Ok(())
+}
+
+/// Contents of the generated code file or `None` if it does not exist
+fn read_generated_code(path: &Path) -> Result<Option<String>, Error> {
+    if !path.exists() {
+        return Ok(None);
+    }
+
+    let bytes = fs::read(path).map_err(|e| Error::io(path, e))?;
+    let source = source_text::decode(path, bytes)?;
+    let (contents, _) = source_text::strip_byte_order_mark(&source);
+    Ok(Some(contents.to_string()))
+}
+
+/// Check the part directive of the generated code file. With `--fix`
+/// the Dart file is fixed and parsed again.
+fn check_part_directive(
+    config: &ArgsConfig,
+    file: DartFile,
+    on_event: &mut dyn FnMut(Event),
+) -> Result<DartFile, Error> {
+    let Some(problem) = part_directive::check_part_directive(&file) else {
+        return Ok(file);
+    };
+
+    if !config.fix {
+        return Err(problem.to_error(&file));
+    }
+
+    // Positions are counted from the end, so the fix can be applied to
+    // the contents with the byte order mark
+    let source = problem.fix.apply(&file.encoded_contents());
+    let fixed_file = DartFile::parse(&file.path, &source)?;
+    write_file(&file.path, &source)?;
+
+    on_event(Event::FileFixed {
+        path: file.path,
+        fix: problem.fix_title,
+    });
+
+    Ok(fixed_file)
+}
+
+pub const NO_ANNOTATED_CLASSES: &str = "no classes annotated with @freezed";
+
+pub fn report_generated_classes(file: &DartFile, on_event: &mut dyn FnMut(Event)) {
+    for class in file.parsed_file.freezed_annotated_classes() {
+        on_event(Event::ClassGenerated {
+            path: file.path.clone(),
+            class_name: class.name.to_string(),
+        });
+    }
+}
+
 pub fn update_generated_code_if_needed(
     config: &ArgsConfig,
     file: DartFile,
     new_generated_code: String,
-) -> Result<()> {
-    let generated_code_path = file
-        .path
-        .with_extension(FREEZED_GENERATED_CODE_FILE_EXTENSION_WITHOUT_LEADING_DOT);
-
-    if generated_code_path.exists() {
-        let current_generated_code = fs::read_to_string(&generated_code_path)?;
+    on_event: &mut dyn FnMut(Event),
+) -> Result<(), Error> {
+    let generated_code_path = generated_code_path(&file.path);
+    let new_generated_code = config
+        .line_ending
+        .apply(&file.contents, &new_generated_code);
 
+    if let Some(current_generated_code) = read_generated_code(&generated_code_path)? {
         if current_generated_code == new_generated_code {
+            on_event(Event::FileUnchanged {
+                path: generated_code_path,
+                source_path: file.path,
+            });
             return Ok(());
         }
 
-        if !config.force && !current_generated_code.starts_with(GENERATED_FILE_HEADER) {
-            return Err(anyhow!(
-                "Generated code file {:?} does not start with the expected header",
-                generated_code_path
-            ));
+        if !config.force && header::parse_header(&current_generated_code) == Header::Unknown {
+            return Err(Error::UnknownGeneratedFileHeader {
+                path: generated_code_path,
+            });
         }
     }
 
-    fs::write(generated_code_path, new_generated_code)?;
+    write_file(&generated_code_path, new_generated_code)?;
+
+    on_event(Event::FileWritten {
+        path: generated_code_path,
+        source_path: file.path,
+    });
 
     Ok(())
 }
+
+/// Write to a temporary file in the same directory and rename it over
+/// `path`, so an interrupted run does not leave a partially written
+/// file. Permissions of an existing file are kept.
+fn write_file(path: &Path, contents: impl AsRef<[u8]>) -> Result<(), Error> {
+    let temporary_path = temporary_path(path);
+    write_and_rename(path, &temporary_path, contents.as_ref()).map_err(|e| {
+        let _ = fs::remove_file(&temporary_path);
+        Error::io(path, e)
+    })
+}
+
+fn write_and_rename(path: &Path, temporary_path: &Path, contents: &[u8]) -> io::Result<()> {
+    let permissions = match fs::metadata(path) {
+        Ok(metadata) => Some(metadata.permissions()),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => None,
+        Err(e) => return Err(e),
+    };
+
+    let mut file = File::create(temporary_path)?;
+    file.write_all(contents)?;
+    file.sync_all()?;
+    drop(file);
+
+    if let Some(permissions) = permissions {
+        fs::set_permissions(temporary_path, permissions)?;
+    }
+    fs::rename(temporary_path, path)
+}
+
+/// Number of the next temporary file of this process
+static NEXT_TEMPORARY_FILE: AtomicUsize = AtomicUsize::new(0);
+
+/// Hidden file next to `path` like `.a.freezed.dart.1234.0.tmp`. The
+/// name contains the process ID and a counter, so concurrent writes
+/// like the language server does do not use the same file.
+fn temporary_path(path: &Path) -> PathBuf {
+    let file_name = path
+        .file_name()
+        .map(|name| name.to_string_lossy())
+        .unwrap_or_default();
+    let number = NEXT_TEMPORARY_FILE.fetch_add(1, Ordering::Relaxed);
+    path.with_file_name(format!(".{}.{}.{}.tmp", file_name, process::id(), number))
+}
+
+#[cfg(test)]
+mod tests {
+    use clap::Parser;
+
+    use super::*;
+    use crate::codegen::GENERATED_FILE_HEADER;
+    use crate::file_finder::parse_freezed_classes_from_dart_files;
+    use crate::test_utils::TempDir;
+
+    const USER: &str = "@freezed
+class User with _$User {
+  factory User({required String name}) = _User;
+}
+";
+
+    fn config(args: &[&str]) -> ArgsConfig {
+        let mut all_args = vec!["icegen", "--code-dir", "lib"];
+        all_args.extend(args);
+        ArgsConfig::parse_from(all_args)
+    }
+
+    fn dart_file(path: &Path) -> DartFile {
+        DartFile::parse(path, &fs::read_to_string(path).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn missing_part_directive_makes_failure_without_fix() {
+        let dir = TempDir::new();
+        let path = dir.write("lib/user.dart", USER);
+        let mut events = vec![];
+
+        let error = check_part_directive(&config(&[]), dart_file(&path), &mut |e| events.push(e))
+            .unwrap_err();
+
+        assert!(matches!(error, Error::PartDirective { .. }));
+        assert_eq!(fs::read_to_string(&path).unwrap(), USER);
+        assert!(events.is_empty());
+    }
+
+    #[test]
+    fn missing_part_directive_is_added_with_fix() {
+        let dir = TempDir::new();
+        let path = dir.write("lib/user.dart", USER);
+        let mut events = vec![];
+
+        let fixed_file = check_part_directive(&config(&["--fix"]), dart_file(&path), &mut |e| {
+            events.push(e)
+        })
+        .unwrap();
+
+        let contents = fs::read_to_string(&path).unwrap();
+        assert!(contents.contains("part 'user.freezed.dart';"));
+        assert_eq!(fixed_file.contents, contents);
+        assert!(part_directive::check_part_directive(&fixed_file).is_none());
+        assert!(matches!(
+            &events[..],
+            [Event::FileFixed { path: fixed_path, .. }] if *fixed_path == path
+        ));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn write_file_keeps_permissions() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = TempDir::new();
+        let path = dir.write("lib/user.freezed.dart", "old");
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o640)).unwrap();
+
+        write_file(&path, "new").unwrap();
+
+        assert_eq!(fs::read_to_string(&path).unwrap(), "new");
+        let mode = fs::metadata(&path).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o640);
+    }
+
+    #[test]
+    fn write_file_removes_temporary_file_on_failure() {
+        let dir = TempDir::new();
+        // Renaming a file over a directory fails
+        let path = dir.path().join("user.freezed.dart");
+        fs::create_dir(&path).unwrap();
+        dir.write("user.freezed.dart/a.dart", "");
+
+        assert!(matches!(write_file(&path, "new"), Err(Error::Io { .. })));
+        let names = fs::read_dir(dir.path())
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name())
+            .collect::<Vec<_>>();
+        assert_eq!(names, ["user.freezed.dart"]);
+        assert!(path.is_dir());
+    }
+
+    #[test]
+    fn temporary_paths_are_unique_in_process() {
+        let path = Path::new("lib/user.freezed.dart");
+        let first = temporary_path(path);
+        assert_ne!(first, temporary_path(path));
+        assert_eq!(first.parent(), path.parent());
+    }
+
+    fn generated_code(file: &DartFile) -> String {
+        generate_data_class_file(file, &DiagnosticableLibraries::new(&[], false)).unwrap()
+    }
+
+    fn check(code_dir: &Path) -> (Vec<Error>, Vec<Event>) {
+        let mut errors = ErrorCollector::new(false);
+        let mut events = vec![];
+        let files =
+            parse_freezed_classes_from_dart_files(code_dir, &mut errors, &mut |_| ()).unwrap();
+        check_generated_code_for_parsed_files(files, &mut errors, &mut |e| events.push(e)).unwrap();
+        (errors.errors, events)
+    }
+
+    #[test]
+    fn check_finds_stale_generated_code() {
+        let dir = TempDir::new();
+        let source = format!("part 'user.freezed.dart';\n\n{}", USER);
+        let path = dir.write("lib/user.dart", &source);
+        let generated_code_path = generated_code_path(&path);
+
+        let (errors, _) = check(dir.path());
+        assert!(
+            matches!(&errors[..], [Error::StaleGeneratedFile { path }] if *path == generated_code_path)
+        );
+
+        let file = dart_file(&path);
+        let code = generated_code(&file);
+        update_generated_code_if_needed(&config(&[]), file, code, &mut |_| ()).unwrap();
+        let (errors, events) = check(dir.path());
+        assert!(errors.is_empty());
+        assert!(
+            matches!(&events[..], [Event::FileUnchanged { path, .. }] if *path == generated_code_path)
+        );
+
+        dir.write("lib/user.dart", &source.replace("name", "title"));
+        let (errors, _) = check(dir.path());
+        assert!(matches!(&errors[..], [Error::StaleGeneratedFile { .. }]));
+    }
+
+    #[test]
+    fn legacy_header_is_overwritten_without_force() {
+        let dir = TempDir::new();
+        let path = dir.write("lib/user.dart", USER);
+        let legacy = format!("{}\npart of 'user.dart';\n", GENERATED_FILE_HEADER);
+        let generated_code_path = dir.write("lib/user.freezed.dart", &legacy);
+        let file = dart_file(&path);
+        let code = generated_code(&file);
+        let mut events = vec![];
+
+        update_generated_code_if_needed(&config(&[]), file, code.clone(), &mut |e| events.push(e))
+            .unwrap();
+
+        assert_eq!(fs::read_to_string(&generated_code_path).unwrap(), code);
+        assert!(matches!(&events[..], [Event::FileWritten { .. }]));
+    }
+
+    #[test]
+    fn unknown_header_is_not_overwritten_without_force() {
+        let dir = TempDir::new();
+        let path = dir.write("lib/user.dart", USER);
+        let generated_code_path = dir.write("lib/user.freezed.dart", "part of 'user.dart';\n");
+        let file = dart_file(&path);
+        let code = generated_code(&file);
+
+        let error =
+            update_generated_code_if_needed(&config(&[]), file, code, &mut |_| ()).unwrap_err();
+
+        assert!(matches!(error, Error::UnknownGeneratedFileHeader { .. }));
+        assert_eq!(
+            fs::read_to_string(&generated_code_path).unwrap(),
+            "part of 'user.dart';\n"
+        );
+    }
+}
@@ -0,0 +1,326 @@
+//! Icegen generates Dart data classes which are partially compatible
+//! with [Freezed](https://github.com/rrousselGit/freezed).
+//!
+//! Generate code for a Dart file:
+//!
+//! ```
+//! use icegen::{generate_from_string, DiagnosticableLibraries};
+//!
+//! let source = "
+//! @freezed
+//! class User with _$User {
+//!   factory User({required String name}) = _User;
+//! }
+//! ";
+//!
+//! let libraries = DiagnosticableLibraries::new(&[], false);
+//! let generated = generate_from_string(source, "lib/user.dart", &libraries)
+//!     .unwrap()
+//!     .unwrap();
+//! assert!(generated.contains("part of 'user.dart';"));
+//! ```
+
+use std::io::{self, Read, Write};
+use std::path::{Path, PathBuf};
+
+pub mod config;
+pub mod diagnosticable;
+pub mod error;
+pub mod event;
+pub mod lsp;
+pub mod message_format;
+pub mod parser;
+pub mod source_text;
+pub mod summary;
+
+mod codegen;
+mod file_finder;
+mod file_writer;
+mod lock_file;
+#[cfg(test)]
+mod test_utils;
+
+use crate::error::ErrorCollector;
+use crate::lock_file::WriteLock;
+
+pub use crate::codegen::GENERATED_FILE_HEADER;
+pub use crate::config::ArgsConfig;
+pub use crate::diagnosticable::DiagnosticableLibraries;
+pub use crate::error::{Error, Location};
+pub use crate::event::Event;
+pub use crate::parser::ParsedFile;
+pub use crate::summary::Summary;
+
+/// Generate contents of the `.freezed.dart` file for Dart source code.
+///
+/// `path` is the path of the Dart file. Its file name is used in the
+/// `part of` statement and project-local exports are resolved relative
+/// to it. Returns `None` if the source code does not contain classes
+/// annotated with `@freezed`, `@Freezed` or `@unfreezed`. The part
+/// directive of the generated code is only checked when code is
+/// generated for a directory.
+pub fn generate_from_string(
+    source: &str,
+    path: impl AsRef<Path>,
+    libraries: &DiagnosticableLibraries,
+) -> Result<Option<String>, Error> {
+    let generated = generate_dart_file_with_events(source, path.as_ref(), libraries, &mut |_| ())?;
+    Ok(generated.map(|(_, generated_code)| generated_code))
+}
+
+/// Parse and generate code for Dart file `path` containing `source`.
+/// Returns the parsed file and the generated code, or `None` if the file
+/// does not contain annotated classes.
+pub(crate) fn generate_dart_file_with_events(
+    source: &str,
+    path: &Path,
+    libraries: &DiagnosticableLibraries,
+    on_event: &mut dyn FnMut(Event),
+) -> Result<Option<(file_finder::DartFile, String)>, Error> {
+    let file = file_finder::DartFile::parse(path, source)?;
+    on_event(Event::FileParsed {
+        path: PathBuf::from(path),
+    });
+
+    if !file.parsed_file.contains_freezed_annotated_class() {
+        on_event(Event::FileSkipped {
+            path: PathBuf::from(path),
+            reason: file_writer::NO_ANNOTATED_CLASSES.to_string(),
+        });
+        return Ok(None);
+    }
+
+    let generated_code = codegen::generate_data_class_file(&file, libraries)
+        .map_err(|e| Error::generate(Some(file.path.clone()), e))?;
+    file_writer::report_generated_classes(&file, on_event);
+
+    Ok(Some((file, generated_code)))
+}
+
+const STDIN_PATH: &str = "<stdin>";
+const STDOUT_PATH: &str = "<stdout>";
+
+/// Generate code like the `icegen` command does. If [ArgsConfig::stdin]
+/// is set, code is generated from standard input to standard output.
+/// Otherwise code is generated for Dart files in [ArgsConfig::code_dir].
+///
+/// Files which fail do not stop handling of other files unless
+/// [ArgsConfig::fail_fast] is set. Their errors are in the returned
+/// [Summary], so check [Summary::is_success].
+pub fn run(config: &ArgsConfig) -> Result<Summary, Error> {
+    run_with_events(config, &mut |_| ())
+}
+
+/// Same as [run], but `on_event` is called when files are parsed,
+/// classes are generated and files are written or fail.
+pub fn run_with_events(
+    config: &ArgsConfig,
+    on_event: &mut dyn FnMut(Event),
+) -> Result<Summary, Error> {
+    let mut summary = Summary::default();
+    let mut on_event = |event: Event| {
+        summary.record(&event);
+        on_event(event);
+    };
+
+    if config.stdin {
+        run_single_file_with_events(
+            config,
+            &mut io::stdin().lock(),
+            &mut io::stdout().lock(),
+            &mut on_event,
+        )?;
+        return Ok(summary);
+    }
+
+    let code_dir = config
+        .code_dir
+        .as_ref()
+        .ok_or_else(|| Error::InvalidConfig {
+            message: "Code directory is required when standard input is not used".to_string(),
+        })?;
+
+    // Missing directory is reported when the files are searched
+    let _lock = code_dir
+        .is_dir()
+        .then(|| WriteLock::acquire(code_dir))
+        .transpose()?;
+
+    let mut errors = ErrorCollector::new(config.fail_fast);
+    let parsed_files =
+        file_finder::parse_freezed_classes_from_dart_files(code_dir, &mut errors, &mut on_event)?;
+    if config.check {
+        file_writer::check_generated_code_for_parsed_files(
+            parsed_files,
+            &mut errors,
+            &mut on_event,
+        )?;
+    } else {
+        file_writer::update_generated_code_for_parsed_files(
+            config,
+            parsed_files,
+            &mut errors,
+            &mut on_event,
+        )?;
+    }
+
+    summary.errors = errors.errors;
+    Ok(summary)
+}
+
+/// Read Dart code of the file [ArgsConfig::file_name] from `input` and
+/// write the generated code to `output`. Nothing is written if the code
+/// does not contain annotated classes.
+pub fn run_single_file(
+    config: &ArgsConfig,
+    input: &mut impl Read,
+    output: &mut impl Write,
+) -> Result<(), Error> {
+    run_single_file_with_events(config, input, output, &mut |_| ())
+}
+
+fn run_single_file_with_events(
+    config: &ArgsConfig,
+    input: &mut impl Read,
+    output: &mut impl Write,
+    on_event: &mut dyn FnMut(Event),
+) -> Result<(), Error> {
+    let path = config
+        .file_name
+        .as_ref()
+        .ok_or_else(|| Error::InvalidConfig {
+            message: "File name is required when standard input is used".to_string(),
+        })?;
+
+    // The output is usually written to the generated code file, so wait
+    // for other runs writing it
+    let _lock = WriteLock::acquire(path)?;
+
+    let mut bytes = vec![];
+    input
+        .read_to_end(&mut bytes)
+        .map_err(|e| Error::io(STDIN_PATH, e))?;
+    let source = source_text::decode(path, bytes)?;
+
+    let libraries = DiagnosticableLibraries::from_config(config);
+
+    if let Some((_, generated_code)) =
+        generate_dart_file_with_events(&source, path, &libraries, on_event)?
+    {
+        let generated_code = config.line_ending.apply(&source, &generated_code);
+        output
+            .write_all(generated_code.as_bytes())
+            .and_then(|_| output.flush())
+            .map_err(|e| Error::io(STDOUT_PATH, e))?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use clap::Parser;
+
+    use super::*;
+    use crate::test_utils::TempDir;
+
+    const USER: &str = "part 'user.freezed.dart';
+
+@freezed
+class User with _$User {
+  factory User({required String name}) = _User;
+}
+";
+
+    fn libraries() -> DiagnosticableLibraries {
+        DiagnosticableLibraries::new(&[], false)
+    }
+
+    #[test]
+    fn generate_from_string_does_not_require_part_directive() {
+        let without_part_directive = USER.replace("part 'user.freezed.dart';\n", "");
+        let generated =
+            generate_from_string(&without_part_directive, "lib/user.dart", &libraries())
+                .unwrap()
+                .unwrap();
+        assert!(generated.contains("part of 'user.dart';"));
+    }
+
+    #[test]
+    fn generate_from_string_ignores_byte_order_mark() {
+        let with_byte_order_mark = format!("\u{feff}{}", USER);
+        assert_eq!(
+            generate_from_string(&with_byte_order_mark, "lib/user.dart", &libraries()).unwrap(),
+            generate_from_string(USER, "lib/user.dart", &libraries()).unwrap(),
+        );
+    }
+
+    fn stdin_config() -> ArgsConfig {
+        ArgsConfig::parse_from(["icegen", "--stdin", "--file-name", "lib/user.dart"])
+    }
+
+    #[test]
+    fn run_single_file_writes_generated_code() {
+        let mut output = vec![];
+        run_single_file(&stdin_config(), &mut USER.as_bytes(), &mut output).unwrap();
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            generate_from_string(USER, "lib/user.dart", &libraries())
+                .unwrap()
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn run_single_file_without_file_name_makes_failure() {
+        let config = ArgsConfig {
+            file_name: None,
+            ..stdin_config()
+        };
+        let mut output = vec![];
+        let error = run_single_file(&config, &mut USER.as_bytes(), &mut output).unwrap_err();
+        assert!(matches!(error, Error::InvalidConfig { .. }));
+        assert!(output.is_empty());
+    }
+
+    #[test]
+    fn run_single_file_without_annotated_classes_writes_nothing() {
+        let mut output = vec![];
+        run_single_file(&stdin_config(), &mut "class A {}".as_bytes(), &mut output).unwrap();
+        assert!(output.is_empty());
+    }
+
+    #[test]
+    fn diagnostics_are_reported_when_files_fail() {
+        let dir = TempDir::new();
+        let failed_path = dir.write(
+            "lib/a.dart",
+            &USER.replace("part 'user.freezed.dart';\n", ""),
+        );
+        dir.write("lib/user.dart", USER);
+
+        let config = ArgsConfig::parse_from(["icegen", "--code-dir", dir.path().to_str().unwrap()]);
+        let mut events = vec![];
+        let summary = run_with_events(&config, &mut |event| events.push(event)).unwrap();
+
+        let failed = events
+            .iter()
+            .position(|event| {
+                *event
+                    == Event::FileFailed {
+                        path: failed_path.clone(),
+                    }
+            })
+            .unwrap();
+        assert!(matches!(
+            &events[failed + 1],
+            Event::Diagnostic {
+                code: "part-directive",
+                path: Some(path),
+                location: Some(_),
+                ..
+            } if *path == failed_path
+        ));
+        assert_eq!(summary.errors.len(), 1);
+    }
+}
@@ -0,0 +1,211 @@
+//! Lock files which prevent concurrent runs writing to the same tree
+//!
+//! Lock files are in a directory of the user, so no files are left in
+//! the code directory and runs of other users do not need access to
+//! them. Only runs of the same user are excluded.
+//!
+//! A run which writes generated code for a directory or a Dart file
+//! locks its path exclusively and all ancestor directories shared. Runs
+//! in the same directory or in parent and child directories wait for
+//! each other, but runs in sibling directories do not. Locks are always
+//! taken from the root to the path, so runs can not deadlock.
+
+use std::fs::{self, File};
+use std::path::{Path, PathBuf};
+
+use crate::codegen::header::source_hash;
+use crate::error::Error;
+
+const LOCK_DIR_NAME: &str = "icegen-locks";
+
+/// Directory of the lock files. It is in the runtime directory of the
+/// user if it is set. Otherwise it is in the temporary directory of the
+/// system, which is shared by users on Unix, so the user ID is added.
+fn lock_dir() -> PathBuf {
+    lock_dir_in(std::env::var_os("XDG_RUNTIME_DIR").map(PathBuf::from))
+}
+
+#[cfg(unix)]
+fn lock_dir_in(runtime_dir: Option<PathBuf>) -> PathBuf {
+    match runtime_dir {
+        Some(runtime_dir) if runtime_dir.is_absolute() => runtime_dir.join(LOCK_DIR_NAME),
+        _ => {
+            // SAFETY: getuid has no preconditions and never fails
+            let uid = unsafe { libc::getuid() };
+            std::env::temp_dir().join(format!("{}-{}", LOCK_DIR_NAME, uid))
+        }
+    }
+}
+
+/// The temporary directory is already specific to the user
+#[cfg(not(unix))]
+fn lock_dir_in(_runtime_dir: Option<PathBuf>) -> PathBuf {
+    std::env::temp_dir().join(LOCK_DIR_NAME)
+}
+
+/// Create the lock directory. Only the user can access it on Unix.
+fn create_lock_dir(lock_dir: &Path) -> std::io::Result<()> {
+    let mut builder = fs::DirBuilder::new();
+    builder.recursive(true);
+    #[cfg(unix)]
+    std::os::unix::fs::DirBuilderExt::mode(&mut builder, 0o700);
+    builder.create(lock_dir)
+}
+
+/// Lock of a directory or a Dart file and its ancestors. The lock is
+/// released when this is dropped or when the process exits.
+pub struct WriteLock {
+    _files: Vec<File>,
+}
+
+impl WriteLock {
+    /// Wait until other runs writing to `path`, its ancestors or its
+    /// descendants have finished and lock it
+    pub fn acquire(path: &Path) -> Result<Self, Error> {
+        Self::acquire_in(&lock_dir(), path)
+    }
+
+    fn acquire_in(lock_dir: &Path, path: &Path) -> Result<Self, Error> {
+        create_lock_dir(lock_dir).map_err(|e| Error::io(lock_dir, e))?;
+
+        let path = canonical_path(path).map_err(|e| Error::io(path, e))?;
+        let mut ancestors = path.ancestors().collect::<Vec<&Path>>();
+        ancestors.reverse();
+
+        let mut files = vec![];
+        for ancestor in ancestors {
+            let lock_path =
+                lock_dir.join(format!("{}.lock", source_hash(&ancestor.to_string_lossy())));
+            let file = File::options()
+                .create(true)
+                .truncate(false)
+                .write(true)
+                .open(&lock_path)
+                .map_err(|e| Error::io(&lock_path, e))?;
+
+            if ancestor == path {
+                file.lock()
+            } else {
+                file.lock_shared()
+            }
+            .map_err(|e| Error::io(&lock_path, e))?;
+
+            files.push(file);
+        }
+
+        Ok(Self { _files: files })
+    }
+}
+
+/// Absolute path with symbolic links resolved. The path does not need
+/// to exist, like the file name of standard input.
+fn canonical_path(path: &Path) -> std::io::Result<PathBuf> {
+    if let Ok(path) = path.canonicalize() {
+        return Ok(path);
+    }
+
+    let path = std::path::absolute(path)?;
+    match (path.parent(), path.file_name()) {
+        (Some(parent), Some(file_name)) => Ok(canonical_path(parent)?.join(file_name)),
+        _ => Ok(path),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::mpsc;
+    use std::thread;
+    use std::time::Duration;
+
+    use super::*;
+    use crate::test_utils::TempDir;
+
+    /// True if `path` can not be locked while `held` is locked
+    fn excluded(lock_dir: &Path, held: &Path, path: &Path) -> bool {
+        let _held = WriteLock::acquire_in(lock_dir, held).unwrap();
+        let (sender, receiver) = mpsc::channel();
+        let (lock_dir, path) = (lock_dir.to_owned(), path.to_owned());
+        thread::spawn(move || {
+            let _lock = WriteLock::acquire_in(&lock_dir, &path).unwrap();
+            let _ = sender.send(());
+        });
+        receiver.recv_timeout(Duration::from_millis(200)).is_err()
+    }
+
+    #[test]
+    fn runs_in_same_tree_exclude_each_other() {
+        let dir = TempDir::new();
+        let lock_dir = dir.path().join("locks");
+        let code_dir = dir.path().join("app");
+        let lib = code_dir.join("lib");
+        fs::create_dir_all(&lib).unwrap();
+
+        assert!(excluded(&lock_dir, &code_dir, &code_dir));
+        assert!(excluded(&lock_dir, &code_dir, &lib));
+        assert!(excluded(&lock_dir, &lib, &code_dir));
+        assert!(excluded(&lock_dir, &code_dir, &lib.join("user.dart")));
+    }
+
+    #[test]
+    fn runs_in_sibling_directories_do_not_exclude_each_other() {
+        let dir = TempDir::new();
+        let lock_dir = dir.path().join("locks");
+        let a = dir.path().join("a");
+        let b = dir.path().join("b");
+        fs::create_dir_all(&a).unwrap();
+        fs::create_dir_all(&b).unwrap();
+
+        assert!(!excluded(&lock_dir, &a, &b));
+        assert!(!excluded(&lock_dir, &a.join("a.dart"), &a.join("b.dart")));
+    }
+
+    #[test]
+    fn no_files_are_left_in_code_directory() {
+        let dir = TempDir::new();
+        let lock_dir = dir.path().join("locks");
+        let code_dir = dir.path().join("app");
+        fs::create_dir_all(&code_dir).unwrap();
+
+        drop(WriteLock::acquire_in(&lock_dir, &code_dir).unwrap());
+
+        assert_eq!(fs::read_dir(&code_dir).unwrap().count(), 0);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn lock_dir_is_private_to_user() {
+        use std::os::unix::fs::{MetadataExt, PermissionsExt};
+
+        let dir = TempDir::new();
+        let lock_dir = dir.path().join("locks");
+        let code_dir = dir.path().join("app");
+        fs::create_dir_all(&code_dir).unwrap();
+
+        drop(WriteLock::acquire_in(&lock_dir, &code_dir).unwrap());
+
+        let metadata = fs::metadata(&lock_dir).unwrap();
+        assert_eq!(metadata.permissions().mode() & 0o777, 0o700);
+        assert_eq!(metadata.uid(), unsafe { libc::getuid() });
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn lock_dir_is_specific_to_user() {
+        let runtime_dir = PathBuf::from("/run/user/1000");
+        assert_eq!(
+            lock_dir_in(Some(runtime_dir.clone())),
+            runtime_dir.join(LOCK_DIR_NAME)
+        );
+
+        let uid = unsafe { libc::getuid() };
+        let temporary_lock_dir = lock_dir_in(None);
+        assert_eq!(
+            temporary_lock_dir,
+            std::env::temp_dir().join(format!("icegen-locks-{}", uid))
+        );
+        assert_eq!(
+            lock_dir_in(Some(PathBuf::from("relative"))),
+            temporary_lock_dir
+        );
+    }
+}
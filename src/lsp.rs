@@ -0,0 +1,245 @@
+//! Language Server Protocol server
+//!
+//! The server publishes diagnostics of open Dart files containing
+//! annotated classes, offers code actions to fix common mistakes and
+//! updates the generated code file when a file is saved.
+
+use std::collections::HashMap;
+use std::io;
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+use tokio::io::{AsyncRead, AsyncWrite};
+use tower_lsp::jsonrpc::Result;
+use tower_lsp::lsp_types::*;
+use tower_lsp::{Client, LanguageServer, LspService, Server};
+
+use crate::{
+    config::ArgsConfig, diagnosticable::DiagnosticableLibraries, error::Error, event::Event,
+    file_writer, lock_file::WriteLock,
+};
+
+use self::code_actions::{code_actions, REGENERATE_COMMAND};
+use self::diagnostics::diagnostics;
+
+mod code_actions;
+mod diagnostics;
+mod text;
+
+const SERVER_NAME: &str = "icegen";
+
+/// Run the server using standard input and output until the client
+/// exits.
+pub fn run_stdio(config: ArgsConfig) -> io::Result<()> {
+    let runtime = tokio::runtime::Runtime::new()?;
+    runtime.block_on(serve(tokio::io::stdin(), tokio::io::stdout(), config));
+    Ok(())
+}
+
+/// Run the server reading requests from `input` and writing responses
+/// to `output` until the client exits.
+pub async fn serve(input: impl AsyncRead + Unpin, output: impl AsyncWrite, config: ArgsConfig) {
+    let (service, socket) = LspService::new(|client| Backend::new(client, config));
+    Server::new(input, output, socket).serve(service).await;
+}
+
+struct Backend {
+    client: Client,
+    libraries: DiagnosticableLibraries,
+    config: ArgsConfig,
+    /// Contents of open documents
+    documents: Mutex<HashMap<Url, String>>,
+}
+
+impl Backend {
+    fn new(client: Client, config: ArgsConfig) -> Self {
+        Self {
+            client,
+            libraries: DiagnosticableLibraries::from_config(&config),
+            config,
+            documents: Mutex::new(HashMap::new()),
+        }
+    }
+
+    fn document(&self, uri: &Url) -> Option<String> {
+        self.documents.lock().unwrap().get(uri).cloned()
+    }
+
+    async fn publish_diagnostics(&self, uri: Url) {
+        let Some(source) = self.document(&uri) else {
+            return;
+        };
+
+        let diagnostics = diagnostics(&source, &file_path(&uri), &self.libraries);
+        self.client
+            .publish_diagnostics(uri, diagnostics, None)
+            .await;
+    }
+
+    /// Update the generated code file of the document. Errors are shown
+    /// as messages.
+    async fn regenerate(&self, uri: &Url) {
+        let Some(source) = self.document(uri) else {
+            return;
+        };
+
+        let path = file_path(uri);
+        let lock_path = path.clone();
+        // Waiting for other runs blocks the thread
+        let lock = tokio::task::spawn_blocking(move || WriteLock::acquire(&lock_path))
+            .await
+            .unwrap_or_else(|e| Err(Error::io(&path, io::Error::other(e))));
+        let mut events = vec![];
+        let result = lock.and_then(|_lock| {
+            crate::generate_dart_file_with_events(&source, &path, &self.libraries, &mut |_| ())
+                .and_then(|generated| match generated {
+                    Some((file, generated_code)) => file_writer::update_generated_code_if_needed(
+                        &self.config,
+                        file,
+                        generated_code,
+                        &mut |event| events.push(event),
+                    ),
+                    None => Ok(()),
+                })
+        });
+
+        match result {
+            Ok(()) => {
+                for event in events {
+                    if let Event::FileWritten { path, .. } = event {
+                        let message = format!("Updated {}", path.display());
+                        self.client.log_message(MessageType::INFO, message).await;
+                    }
+                }
+            }
+            Err(e) => {
+                self.client
+                    .show_message(MessageType::ERROR, e.to_string())
+                    .await;
+            }
+        }
+    }
+}
+
+#[tower_lsp::async_trait]
+impl LanguageServer for Backend {
+    async fn initialize(&self, _: InitializeParams) -> Result<InitializeResult> {
+        Ok(InitializeResult {
+            capabilities: ServerCapabilities {
+                text_document_sync: Some(TextDocumentSyncCapability::Options(
+                    TextDocumentSyncOptions {
+                        open_close: Some(true),
+                        change: Some(TextDocumentSyncKind::FULL),
+                        save: Some(TextDocumentSyncSaveOptions::SaveOptions(SaveOptions {
+                            include_text: Some(true),
+                        })),
+                        ..TextDocumentSyncOptions::default()
+                    },
+                )),
+                code_action_provider: Some(CodeActionProviderCapability::Options(
+                    CodeActionOptions {
+                        code_action_kinds: Some(vec![
+                            CodeActionKind::QUICKFIX,
+                            CodeActionKind::REFACTOR,
+                            CodeActionKind::SOURCE,
+                        ]),
+                        ..CodeActionOptions::default()
+                    },
+                )),
+                execute_command_provider: Some(ExecuteCommandOptions {
+                    commands: vec![REGENERATE_COMMAND.to_string()],
+                    ..ExecuteCommandOptions::default()
+                }),
+                ..ServerCapabilities::default()
+            },
+            server_info: Some(ServerInfo {
+                name: SERVER_NAME.to_string(),
+                version: Some(env!("ICEGEN_BUILD_VERSION").to_string()),
+            }),
+        })
+    }
+
+    async fn shutdown(&self) -> Result<()> {
+        Ok(())
+    }
+
+    async fn did_open(&self, params: DidOpenTextDocumentParams) {
+        let document = params.text_document;
+        self.documents
+            .lock()
+            .unwrap()
+            .insert(document.uri.clone(), document.text);
+        self.publish_diagnostics(document.uri).await;
+    }
+
+    async fn did_change(&self, params: DidChangeTextDocumentParams) {
+        // Full document sync sends the whole document as the last change
+        let Some(change) = params.content_changes.into_iter().last() else {
+            return;
+        };
+
+        let uri = params.text_document.uri;
+        self.documents
+            .lock()
+            .unwrap()
+            .insert(uri.clone(), change.text);
+        self.publish_diagnostics(uri).await;
+    }
+
+    async fn did_save(&self, params: DidSaveTextDocumentParams) {
+        let uri = params.text_document.uri;
+        if let Some(text) = params.text {
+            self.documents.lock().unwrap().insert(uri.clone(), text);
+        }
+
+        self.regenerate(&uri).await;
+        self.publish_diagnostics(uri).await;
+    }
+
+    async fn did_close(&self, params: DidCloseTextDocumentParams) {
+        let uri = params.text_document.uri;
+        self.documents.lock().unwrap().remove(&uri);
+        self.client.publish_diagnostics(uri, vec![], None).await;
+    }
+
+    async fn code_action(&self, params: CodeActionParams) -> Result<Option<CodeActionResponse>> {
+        let uri = params.text_document.uri;
+        let Some(source) = self.document(&uri) else {
+            return Ok(None);
+        };
+
+        Ok(Some(code_actions(
+            &uri,
+            &source,
+            &file_path(&uri),
+            params.range,
+            &params.context.diagnostics,
+        )))
+    }
+
+    async fn execute_command(
+        &self,
+        params: ExecuteCommandParams,
+    ) -> Result<Option<serde_json::Value>> {
+        if params.command != REGENERATE_COMMAND {
+            return Err(tower_lsp::jsonrpc::Error::method_not_found());
+        }
+
+        let uri = params
+            .arguments
+            .first()
+            .and_then(|argument| argument.as_str())
+            .and_then(|uri| Url::parse(uri).ok())
+            .ok_or_else(|| tower_lsp::jsonrpc::Error::invalid_params("Document URI expected"))?;
+
+        self.regenerate(&uri).await;
+        Ok(None)
+    }
+}
+
+/// Path of the document. Only the file name is used for documents
+/// which are not files.
+fn file_path(uri: &Url) -> PathBuf {
+    uri.to_file_path()
+        .unwrap_or_else(|_| PathBuf::from(uri.path()))
+}
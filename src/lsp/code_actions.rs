@@ -0,0 +1,259 @@
+//! Code actions for annotated classes
+
+use std::collections::HashMap;
+use std::path::Path;
+
+use tower_lsp::lsp_types::{
+    CodeAction, CodeActionKind, CodeActionOrCommand, Command, Diagnostic, NumberOrString, Position,
+    Range, TextEdit, Url, WorkspaceEdit,
+};
+
+use crate::{
+    codegen::part_directive::check_part_directive,
+    file_finder::{generated_code_path, DartFile},
+    parser::{ClassDefinition, ClassItem},
+};
+
+use super::text;
+
+/// Command which updates the generated code file. The argument is
+/// the URI of the Dart file.
+pub const REGENERATE_COMMAND: &str = "icegen.regenerate";
+
+/// Code of [crate::Error::Generate] diagnostics
+const GENERATE_ERROR_CODE: &str = "generate";
+
+/// Code actions for annotated classes overlapping `range`. Diagnostics
+/// in `diagnostics` are attached to the quick fixes which fix them.
+pub fn code_actions(
+    uri: &Url,
+    source: &str,
+    path: &Path,
+    range: Range,
+    diagnostics: &[Diagnostic],
+) -> Vec<CodeActionOrCommand> {
+    let Ok(file) = DartFile::parse(path, source) else {
+        return vec![];
+    };
+
+    let start = text::offset(source, range.start);
+    let end = text::offset(source, range.end);
+
+    let classes: Vec<&ClassDefinition> = file.parsed_file.freezed_annotated_classes().collect();
+    if classes.is_empty() {
+        return vec![];
+    }
+
+    let overlapping_classes: Vec<&ClassDefinition> = classes
+        .into_iter()
+        .filter(|class| {
+            start <= class.end_position.offset(source) && end >= class.name_position.offset(source)
+        })
+        .collect();
+
+    let mut actions = vec![];
+
+    if !overlapping_classes.is_empty() {
+        if let Some(problem) = check_part_directive(&file) {
+            let error = problem.to_error(&file);
+            let fix = problem.fix;
+            actions.push(quick_fix(
+                problem.fix_title,
+                uri,
+                TextEdit::new(
+                    Range::new(
+                        text::position(source, fix.start.offset(source)),
+                        text::position(source, fix.end.offset(source)),
+                    ),
+                    fix.text,
+                ),
+                matching_diagnostics(diagnostics, error.code()),
+            ));
+        }
+    }
+
+    for class in overlapping_classes {
+        if missing_mixin(class) {
+            actions.push(quick_fix(
+                format!("Add `with {}`", mixin_name(class)),
+                uri,
+                mixin_edit(source, class),
+                class_name_diagnostics(
+                    diagnostics,
+                    text::position(source, class.name_position.offset(source)),
+                ),
+            ));
+        }
+
+        if let Some((title, edit)) = private_constructor_edit(source, class) {
+            actions.push(action(title, CodeActionKind::REFACTOR, uri, edit));
+        }
+    }
+
+    actions.push(regenerate_action(uri, path));
+
+    actions
+}
+
+fn regenerate_action(uri: &Url, path: &Path) -> CodeActionOrCommand {
+    let file_name = generated_code_path(path)
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let title = format!("Regenerate `{}`", file_name);
+
+    CodeActionOrCommand::CodeAction(CodeAction {
+        title: title.clone(),
+        kind: Some(CodeActionKind::SOURCE),
+        command: Some(Command {
+            title,
+            command: REGENERATE_COMMAND.to_string(),
+            arguments: Some(vec![uri.to_string().into()]),
+        }),
+        ..CodeAction::default()
+    })
+}
+
+/// Name of the generated mixin like `_$User`
+fn mixin_name(class: &ClassDefinition) -> String {
+    format!("_${}", class.name)
+}
+
+fn missing_mixin(class: &ClassDefinition) -> bool {
+    let mixin_name = mixin_name(class);
+    !class
+        .mixin_types
+        .iter()
+        .any(|mixin| mixin.base_name() == Some(mixin_name.as_str()))
+}
+
+/// Add the generated mixin as the first mixin
+fn mixin_edit(source: &str, class: &ClassDefinition) -> TextEdit {
+    let mixin_name = mixin_name(class);
+
+    match class.mixin_types_position {
+        Some(position) => TextEdit::new(
+            text::range(source, position.offset(source), 0),
+            format!("{}, ", mixin_name),
+        ),
+        None => {
+            let name_end = class.name_position.offset(source) + class.name.as_str().len();
+            TextEdit::new(
+                text::range(source, name_end, 0),
+                format!(" with {}", mixin_name),
+            )
+        }
+    }
+}
+
+/// Add `X._();` as the first item of the class body if the class does
+/// not have a private constructor. The constructor is const if the
+/// factory constructor is const.
+fn private_constructor_edit(source: &str, class: &ClassDefinition) -> Option<(String, TextEdit)> {
+    let mut factory_is_const = false;
+    for item in &class.item_info.items {
+        match item {
+            ClassItem::PrivateConstructor(_) => return None,
+            ClassItem::FactoryConstructor(factory) => factory_is_const |= factory.is_const,
+            ClassItem::Member(_) => (),
+        }
+    }
+
+    let constructor = format!(
+        "{}{}._();",
+        if factory_is_const { "const " } else { "" },
+        class.name
+    );
+
+    let body_start = class.body_position.offset(source) + "{".len();
+    Some((
+        format!("Add private constructor `{}`", constructor),
+        TextEdit::new(
+            text::range(source, body_start, 0),
+            format!("\n  {}\n", constructor),
+        ),
+    ))
+}
+
+fn matching_diagnostics(diagnostics: &[Diagnostic], code: &str) -> Vec<Diagnostic> {
+    diagnostics
+        .iter()
+        .filter(|diagnostic| diagnostic.code == Some(NumberOrString::String(code.to_string())))
+        .cloned()
+        .collect()
+}
+
+/// Errors of a class like the missing mixin are reported at the class
+/// name
+fn class_name_diagnostics(diagnostics: &[Diagnostic], position: Position) -> Vec<Diagnostic> {
+    matching_diagnostics(diagnostics, GENERATE_ERROR_CODE)
+        .into_iter()
+        .filter(|diagnostic| diagnostic.range.start == position)
+        .collect()
+}
+
+fn quick_fix(
+    title: String,
+    uri: &Url,
+    edit: TextEdit,
+    diagnostics: Vec<Diagnostic>,
+) -> CodeActionOrCommand {
+    CodeActionOrCommand::CodeAction(CodeAction {
+        is_preferred: Some(true),
+        diagnostics: (!diagnostics.is_empty()).then_some(diagnostics),
+        ..code_action(title, CodeActionKind::QUICKFIX, uri, edit)
+    })
+}
+
+fn action(title: String, kind: CodeActionKind, uri: &Url, edit: TextEdit) -> CodeActionOrCommand {
+    CodeActionOrCommand::CodeAction(code_action(title, kind, uri, edit))
+}
+
+fn code_action(title: String, kind: CodeActionKind, uri: &Url, edit: TextEdit) -> CodeAction {
+    CodeAction {
+        title,
+        kind: Some(kind),
+        edit: Some(WorkspaceEdit {
+            changes: Some(HashMap::from([(uri.clone(), vec![edit])])),
+            ..WorkspaceEdit::default()
+        }),
+        ..CodeAction::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::parser::{ParsedFile, TopLevelItems};
+
+    use super::*;
+
+    fn edit(source: &str) -> TextEdit {
+        let parsed_file = ParsedFile::parse_dart_file(source).unwrap();
+        match &parsed_file.items[..] {
+            [TopLevelItems::Class(class)] => mixin_edit(source, class),
+            _ => panic!("class not parsed"),
+        }
+    }
+
+    #[test]
+    fn mixin_is_added_before_other_mixins() {
+        assert_eq!(
+            edit("class A /* with */ with /* c */ B {}"),
+            TextEdit::new(
+                Range::new(Position::new(0, 32), Position::new(0, 32)),
+                "_$A, ".to_string()
+            )
+        );
+    }
+
+    #[test]
+    fn with_clause_is_added_after_class_name() {
+        assert_eq!(
+            edit("class A {}"),
+            TextEdit::new(
+                Range::new(Position::new(0, 7), Position::new(0, 7)),
+                " with _$A".to_string()
+            )
+        );
+    }
+}
@@ -0,0 +1,115 @@
+//! Diagnostics of open Dart files
+
+use std::path::Path;
+
+use tower_lsp::lsp_types::{Diagnostic, DiagnosticSeverity, NumberOrString};
+
+use crate::{
+    codegen::{generate_data_class_file, part_directive::check_part_directive},
+    diagnosticable::DiagnosticableLibraries,
+    error::{Error, Location},
+    file_finder::DartFile,
+};
+
+use super::text;
+
+pub const SOURCE: &str = "icegen";
+
+pub fn diagnostics(
+    source: &str,
+    path: &Path,
+    libraries: &DiagnosticableLibraries,
+) -> Vec<Diagnostic> {
+    let file = match DartFile::parse(path, source) {
+        Ok(file) => file,
+        Err(e) => return vec![error_diagnostic(source, &e)],
+    };
+
+    if !file.parsed_file.contains_freezed_annotated_class() {
+        return vec![];
+    }
+
+    let mut diagnostics = vec![];
+
+    if let Err(e) = generate_data_class_file(&file, libraries) {
+        let error = Error::generate(Some(file.path.clone()), e);
+        diagnostics.push(error_diagnostic(source, &error));
+    }
+
+    if let Some(problem) = check_part_directive(&file) {
+        diagnostics.push(error_diagnostic(source, &problem.to_error(&file)));
+    }
+
+    diagnostics
+}
+
+/// Diagnostic at the location of the error. Errors without location
+/// are about the whole file, so they are shown on the first line.
+fn error_diagnostic(source: &str, error: &Error) -> Diagnostic {
+    let (range, message) = match error.location() {
+        Some(location) => (text::location_range(source, location), error.message()),
+        None => (
+            text::location_range(source, Location { line: 1, column: 1 }),
+            format!("Error in the whole file: {}", error.message()),
+        ),
+    };
+
+    Diagnostic {
+        range,
+        severity: Some(DiagnosticSeverity::ERROR),
+        code: Some(NumberOrString::String(error.code().to_string())),
+        source: Some(SOURCE.to_string()),
+        message,
+        ..Diagnostic::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tower_lsp::lsp_types::{Position, Range};
+
+    use super::*;
+
+    fn diagnostics_of(source: &str) -> Vec<Diagnostic> {
+        diagnostics(
+            source,
+            Path::new("lib/a.dart"),
+            &DiagnosticableLibraries::new(&[], false),
+        )
+    }
+
+    #[test]
+    fn class_errors_are_at_class_name() {
+        let source = "part 'a.freezed.dart';
+
+@freezed
+class A with _$A {
+  factory A() = _A;
+}
+";
+        let diagnostics = diagnostics_of(source);
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(
+            diagnostics[0].message,
+            "Factory constructor in class A has no parameters"
+        );
+        assert_eq!(diagnostics[0].range.start, Position::new(3, 6));
+    }
+
+    #[test]
+    fn errors_without_location_are_on_first_line() {
+        let source = "part 'a.freezed.dart';\n\nclass A {}\n";
+        let error = Error::Generate {
+            path: None,
+            location: None,
+            message: "Invalid".to_string(),
+        };
+
+        let diagnostic = error_diagnostic(source, &error);
+        assert_eq!(
+            diagnostic.range,
+            Range::new(Position::new(0, 0), Position::new(0, 22))
+        );
+        assert_eq!(diagnostic.message, "Error in the whole file: Invalid");
+    }
+}
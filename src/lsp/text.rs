@@ -0,0 +1,102 @@
+//! Convert between byte offsets and LSP positions
+//!
+//! LSP positions are zero-based and columns are counted in UTF-16
+//! code units.
+
+use tower_lsp::lsp_types::{Position, Range};
+
+use crate::error::Location;
+
+/// Position of byte offset `offset` in `source`
+pub fn position(source: &str, offset: usize) -> Position {
+    let offset = floor_char_boundary(source, offset);
+    let before = &source[..offset];
+    let line = before.matches('\n').count();
+    let line_start = before.rfind('\n').map(|i| i + 1).unwrap_or(0);
+    let character = before[line_start..].encode_utf16().count();
+
+    Position::new(line as u32, character as u32)
+}
+
+/// Byte offset of `position` in `source`. Positions after the end of a
+/// line are moved to the end of the line.
+pub fn offset(source: &str, position: Position) -> usize {
+    let mut line_start = 0;
+    for _ in 0..position.line {
+        match source[line_start..].find('\n') {
+            Some(i) => line_start += i + 1,
+            None => return source.len(),
+        }
+    }
+
+    let line = source[line_start..].split('\n').next().unwrap_or_default();
+    let mut utf16_count = 0;
+    for (i, c) in line.char_indices() {
+        if utf16_count >= position.character as usize {
+            return line_start + i;
+        }
+        utf16_count += c.len_utf16();
+    }
+
+    line_start + line.len()
+}
+
+/// Range from one-based `location` to the end of its line
+pub fn location_range(source: &str, location: Location) -> Range {
+    let start = Position::new(
+        location.line.saturating_sub(1) as u32,
+        location.column.saturating_sub(1) as u32,
+    );
+    // Location columns are characters, not UTF-16 code units
+    let line_start = offset(source, Position::new(start.line, 0));
+    let line = source[line_start..].split('\n').next().unwrap_or_default();
+    let start_offset = line_start
+        + line
+            .char_indices()
+            .nth(start.character as usize)
+            .map(|(i, _)| i)
+            .unwrap_or(line.len());
+    let end_offset = line_start + line.trim_end().len().max(start_offset - line_start);
+
+    Range::new(position(source, start_offset), position(source, end_offset))
+}
+
+/// Range of `len` bytes starting at byte offset `offset`
+pub fn range(source: &str, offset: usize, len: usize) -> Range {
+    Range::new(position(source, offset), position(source, offset + len))
+}
+
+fn floor_char_boundary(source: &str, offset: usize) -> usize {
+    let mut offset = offset.min(source.len());
+    while !source.is_char_boundary(offset) {
+        offset -= 1;
+    }
+    offset
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn position_counts_utf16_code_units() {
+        let source = "a\n😀b";
+        assert_eq!(position(source, source.len() - 1), Position::new(1, 2));
+    }
+
+    #[test]
+    fn offset_is_inverse_of_position() {
+        let source = "a\n😀b\nc";
+        for offset_in_source in [0, 1, 2, 6, 7, 8] {
+            let p = position(source, offset_in_source);
+            assert_eq!(offset(source, p), offset_in_source);
+        }
+    }
+
+    #[test]
+    fn location_range_ends_at_end_of_line() {
+        let source = "a\n  ä @Default(1)  \nb";
+        let range = location_range(source, Location { line: 2, column: 5 });
+        assert_eq!(range, Range::new(Position::new(1, 4), Position::new(1, 15)));
+    }
+}
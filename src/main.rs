@@ -1,27 +1,97 @@
-pub mod codegen;
-pub mod config;
-pub mod file_finder;
-pub mod file_writer;
-pub mod parser;
+use std::io::Write;
 
-// TODO: Handle collection equality in generated code
+use icegen::config::Command;
+use icegen::message_format::{error_to_json, event_to_json, MessageFormat};
+use icegen::{Error, Event};
 
 fn main() {
-    let config = config::get_config();
+    let config = icegen::config::get_config();
 
-    let parsed_files = match file_finder::parse_freezed_classes_from_dart_files(&config.code_dir) {
-        Ok(parsed_files) => parsed_files,
-        Err(e) => {
+    if config.command == Some(Command::Lsp) {
+        if let Err(e) = icegen::lsp::run_stdio(config) {
             eprintln!("Error: {}", e);
             std::process::exit(1);
         }
-    };
+        return;
+    }
+
+    if config.message_format == MessageFormat::Json {
+        // Standard output contains the generated code when --stdin is used
+        let mut output: Box<dyn Write> = if config.stdin {
+            Box::new(std::io::stderr())
+        } else {
+            Box::new(std::io::stdout())
+        };
 
-    match file_writer::update_generated_code_for_parsed_files(&config, parsed_files) {
-        Ok(_) => (),
+        // Errors of failed files are diagnostic events. Only the error
+        // which stopped the run is left.
+        let result = icegen::run_with_events(&config, &mut |event| {
+            let _ = writeln!(output, "{}", event_to_json(&event));
+        });
+
+        match result {
+            Ok(summary) if summary.is_success() => (),
+            Ok(_) => std::process::exit(1),
+            Err(e) => {
+                let _ = writeln!(output, "{}", error_to_json(&e));
+                std::process::exit(1);
+            }
+        }
+        return;
+    }
+
+    let result = icegen::run_with_events(&config, &mut |event| {
+        if let Event::FileFixed { path, fix } = event {
+            println!("Fixed {}: {}", path.display(), fix);
+        }
+    });
+
+    match result {
+        // Standard output contains only the generated code
+        Ok(_) if config.stdin => (),
+        Ok(summary) => {
+            for e in &summary.errors {
+                eprintln!("Error: {}", e);
+            }
+            if !summary.errors.is_empty() {
+                eprintln!();
+            }
+            println!("{}", summary);
+
+            if !summary.is_success() {
+                std::process::exit(1);
+            }
+        }
         Err(e) => {
-            eprintln!("Error: {}", e);
+            if config.stdin {
+                eprintln!("{}", diagnostic_line(&e));
+            } else {
+                eprintln!("Error: {}", e);
+            }
             std::process::exit(1);
         }
     }
 }
+
+/// Error as line like `FILE:LINE:COLUMN: error: MESSAGE`, which editors
+/// can parse. Line and column are left out if they are not known.
+fn diagnostic_line(error: &Error) -> String {
+    let mut line = String::new();
+
+    if let Some(path) = error.path() {
+        line.push_str(&format!("{}:", path.display()));
+    }
+
+    if let Some(location) = error.location() {
+        line.push_str(&format!("{}:{}:", location.line, location.column));
+    }
+
+    if !line.is_empty() {
+        line.push(' ');
+    }
+
+    // Messages are kept on one line
+    let message = error.message().replace('\n', " ");
+    line.push_str(&format!("error: {}", message));
+    line
+}
@@ -0,0 +1,163 @@
+//! Machine-readable output of events and errors
+
+use std::path::Path;
+
+use clap::ValueEnum;
+use serde_json::{json, Value};
+
+use crate::{event::Event, Error};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum)]
+pub enum MessageFormat {
+    /// Errors as human-readable text
+    #[default]
+    Human,
+    /// Events and errors as JSON objects, one per line
+    Json,
+}
+
+/// Event as one line JSON object like
+/// `{"event":"file-parsed","path":"lib/a.dart"}`
+pub fn event_to_json(event: &Event) -> String {
+    let value = match event {
+        Event::FileParsed { path } => json!({
+            "event": "file-parsed",
+            "path": path_value(path),
+        }),
+        Event::ClassGenerated { path, class_name } => json!({
+            "event": "class-generated",
+            "path": path_value(path),
+            "class": class_name,
+        }),
+        Event::FileWritten { path, source_path } => json!({
+            "event": "file-written",
+            "path": path_value(path),
+            "source_path": path_value(source_path),
+        }),
+        Event::FileUnchanged { path, source_path } => json!({
+            "event": "file-unchanged",
+            "path": path_value(path),
+            "source_path": path_value(source_path),
+        }),
+        Event::FileFixed { path, fix } => json!({
+            "event": "file-fixed",
+            "path": path_value(path),
+            "fix": fix,
+        }),
+        Event::FileSkipped { path, reason } => json!({
+            "event": "file-skipped",
+            "path": path_value(path),
+            "reason": reason,
+        }),
+        Event::FileFailed { path } => json!({
+            "event": "file-failed",
+            "path": path_value(path),
+        }),
+        Event::Diagnostic {
+            code,
+            path,
+            location,
+            message,
+        } => {
+            // Span contains the start location if it is known
+            let span = match location {
+                Some(location) => json!({
+                    "start": {
+                        "line": location.line,
+                        "column": location.column,
+                    },
+                }),
+                None => Value::Null,
+            };
+
+            json!({
+                "event": "diagnostic",
+                "severity": "error",
+                "code": code,
+                "path": path.as_deref().map(path_value),
+                "span": span,
+                "message": message,
+            })
+        }
+    };
+
+    value.to_string()
+}
+
+/// Path as JSON string. Paths which are not valid Unicode are
+/// converted lossily, because JSON strings must be valid Unicode.
+fn path_value(path: &Path) -> Value {
+    Value::String(path.to_string_lossy().into_owned())
+}
+
+/// Error as one line JSON object like [Event::Diagnostic]
+pub fn error_to_json(error: &Error) -> String {
+    event_to_json(&Event::diagnostic(error))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use crate::Location;
+
+    use super::*;
+
+    #[test]
+    fn events_are_json_objects() {
+        assert_eq!(
+            event_to_json(&Event::FileParsed {
+                path: PathBuf::from("lib/a.dart"),
+            }),
+            r#"{"event":"file-parsed","path":"lib/a.dart"}"#
+        );
+        assert_eq!(
+            event_to_json(&Event::FileWritten {
+                path: PathBuf::from("lib/a.freezed.dart"),
+                source_path: PathBuf::from("lib/a.dart"),
+            }),
+            r#"{"event":"file-written","path":"lib/a.freezed.dart","source_path":"lib/a.dart"}"#
+        );
+        assert_eq!(
+            event_to_json(&Event::ClassGenerated {
+                path: PathBuf::from("lib/a.dart"),
+                class_name: "A".to_string(),
+            }),
+            r#"{"class":"A","event":"class-generated","path":"lib/a.dart"}"#
+        );
+    }
+
+    #[test]
+    fn errors_are_diagnostics() {
+        assert_eq!(
+            error_to_json(&Error::Generate {
+                path: Some(PathBuf::from("lib/a.dart")),
+                location: Some(Location { line: 2, column: 3 }),
+                message: "Invalid class".to_string(),
+            }),
+            r#"{"code":"generate","event":"diagnostic","message":"Invalid class","path":"lib/a.dart","severity":"error","span":{"start":{"column":3,"line":2}}}"#
+        );
+        assert_eq!(
+            error_to_json(&Error::InvalidConfig {
+                message: "Invalid".to_string(),
+            }),
+            r#"{"code":"invalid-config","event":"diagnostic","message":"Invalid","path":null,"severity":"error","span":null}"#
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn paths_which_are_not_unicode_are_converted_lossily() {
+        use std::ffi::OsStr;
+        use std::os::unix::ffi::OsStrExt;
+
+        let path = PathBuf::from(OsStr::from_bytes(b"lib/\xff.dart"));
+        let event: Value =
+            serde_json::from_str(&event_to_json(&Event::FileFailed { path: path.clone() }))
+                .unwrap();
+        assert_eq!(event["path"], "lib/\u{fffd}.dart");
+        let error: Value =
+            serde_json::from_str(&error_to_json(&Error::StaleGeneratedFile { path })).unwrap();
+        assert_eq!(error["path"], "lib/\u{fffd}.dart");
+    }
+}
@@ -1,34 +1,68 @@
 //! Parse Dart files
 
-use anyhow::Result;
 use nom::{branch::alt, IResult, Parser};
 
-use self::{class::class, import::import_statement, utils::item_parser};
+use crate::error::{Error, Location};
+
+use self::{
+    class::class,
+    enumeration::enum_definition,
+    export::export_statement,
+    import::import_statement,
+    library::library_directive,
+    part::{part_directive, part_of_directive},
+    utils::item_parser,
+};
 
 mod annotation;
 mod class;
 mod data_type;
+mod enumeration;
+mod export;
+mod expression;
 mod identifier;
 mod import;
 mod keyword;
+mod library;
 mod literal;
+mod part;
+mod position;
 mod utils;
 mod whitespace;
 
 pub use self::annotation::Annotation;
+pub use self::annotation::AnnotationArgument;
 pub use self::class::constructor::FactoryConstructor;
 pub use self::class::constructor::PrivateConstructor;
 pub use self::class::items::ClassItem;
 pub use self::class::items::ClassItemInfo;
-pub use self::class::named_parameters::NamedParameter;
+pub use self::class::member::ClassMember;
+pub use self::class::parameters::Parameter;
+pub use self::class::parameters::ParameterKind;
 pub use self::class::ClassDefinition;
+pub use self::data_type::DataType;
+pub use self::enumeration::EnumDefinition;
+pub use self::export::ExportStatement;
+pub use self::expression::expression_kind;
+pub use self::expression::ExpressionKind;
 pub use self::identifier::Identifier;
+pub use self::import::Combinator;
+pub use self::import::ImportConfiguration;
 pub use self::import::ImportStatement;
+pub use self::library::LibraryDirective;
+pub use self::part::PartDirective;
+pub use self::part::PartOfDirective;
+pub use self::position::SourcePosition;
 
 #[derive(Debug, PartialEq)]
 pub enum TopLevelItems {
+    Library(LibraryDirective),
     Import(ImportStatement),
+    Export(ExportStatement),
+    Part(PartDirective),
+    PartOf(PartOfDirective),
     Class(ClassDefinition),
+    Enum(EnumDefinition),
 }
 
 #[derive(Debug, PartialEq)]
@@ -38,29 +72,54 @@ pub struct ParsedFile {
 
 impl ParsedFile {
     pub fn contains_freezed_annotated_class(&self) -> bool {
-        self.items.iter().any(|item| {
-            if let TopLevelItems::Class(class) = item {
-                class.contains_freezed_annotation()
-            } else {
-                false
-            }
+        self.freezed_annotated_classes().next().is_some()
+    }
+
+    pub fn freezed_annotated_classes(&self) -> impl Iterator<Item = &ClassDefinition> {
+        self.items.iter().filter_map(|item| match item {
+            TopLevelItems::Class(class) if class.contains_freezed_annotation() => Some(class),
+            _ => None,
         })
     }
 }
 
 impl ParsedFile {
-    pub fn parse_dart_file(input: &str) -> Result<ParsedFile> {
-        let (_, parsed_file) = parse_file_contents(input).map_err(|e| e.to_owned())?;
+    pub fn parse_dart_file(input: &str) -> Result<ParsedFile, Error> {
+        let (_, parsed_file) = parse_file_contents(input).map_err(|e| parse_error(input, e))?;
         Ok(parsed_file)
     }
 }
 
+/// Error of parsing `source` located where the parser failed
+fn parse_error(source: &str, error: nom::Err<nom::error::Error<&str>>) -> Error {
+    // Incomplete input has no position
+    let location = match &error {
+        nom::Err::Error(e) | nom::Err::Failure(e) => {
+            let (line, column) =
+                SourcePosition::from_remaining_input(e.input).line_and_column(source);
+            Some(Location { line, column })
+        }
+        nom::Err::Incomplete(_) => None,
+    };
+
+    Error::Parse {
+        path: None,
+        location,
+        message: error.to_owned().to_string(),
+    }
+}
+
 pub fn parse_file_contents(input: &str) -> IResult<&str, ParsedFile> {
     item_parser(
         |input| input.is_empty(),
         alt((
+            library_directive.map(TopLevelItems::Library),
             import_statement.map(TopLevelItems::Import),
+            export_statement.map(TopLevelItems::Export),
+            part_of_directive.map(TopLevelItems::PartOf),
+            part_directive.map(TopLevelItems::Part),
             class.map(TopLevelItems::Class),
+            enum_definition.map(TopLevelItems::Enum),
         )),
         input,
     )
@@ -73,21 +132,63 @@ mod tests {
 
     use super::*;
 
+    // Items of `SOURCE`
+    const SOURCE: &str = "
+
+            library a;
+
+            import 'a';
+            import 'b';
+
+            part 'a.freezed.dart';
+
+            test//class A {}
+            class B {}
+            sealed class C {}
+
+            ";
+
     fn i(path: &str) -> TopLevelItems {
         TopLevelItems::Import(ImportStatement {
             path: path.to_string(),
+            configurations: vec![],
+            deferred: false,
+            prefix: None,
+            combinators: vec![],
+            end_position: SourcePosition::after(SOURCE, &format!("import '{}';", path)),
+        })
+    }
+
+    fn l(name: &str) -> TopLevelItems {
+        TopLevelItems::Library(LibraryDirective {
+            name: Some(name.to_string()),
+            end_position: SourcePosition::after(SOURCE, &format!("library {};", name)),
+        })
+    }
+
+    fn p(path: &str) -> TopLevelItems {
+        let directive = format!("part '{}';", path);
+        TopLevelItems::Part(PartDirective {
+            path: path.to_string(),
+            position: SourcePosition::of(SOURCE, &directive),
+            end_position: SourcePosition::after(SOURCE, &directive),
         })
     }
 
     fn c(name: &str) -> TopLevelItems {
+        let declaration = format!("{} {{}}", name);
         TopLevelItems::Class(ClassDefinition {
             annotations: vec![],
             name: Identifier {
                 name: name.to_string(),
             },
             mixin_types: vec![],
+            mixin_types_position: None,
             implemented_types: vec![],
             item_info: ClassItemInfo { items: vec![] },
+            name_position: SourcePosition::of(SOURCE, &declaration),
+            body_position: SourcePosition::after(SOURCE, &format!("{} ", name)),
+            end_position: SourcePosition::after(SOURCE, &declaration),
         })
     }
 
@@ -102,23 +203,25 @@ mod tests {
     #[test]
     fn multiple_classes_and_imports_and_unknown_content() {
         let wanted = ParsedFile {
-            items: vec![i("a"), i("b"), c("B"), c("C")],
+            items: vec![l("a"), i("a"), i("b"), p("a.freezed.dart"), c("B"), c("C")],
         };
 
-        assert_eq!(
-            parse_file_contents(
-                "
-
-            import 'a';
-            import 'b';
-
-            test//class A {}
-            class B {}
-            sealed class C {}
+        assert_eq!(parse_file_contents(SOURCE), Ok(("", wanted)));
+    }
 
-            "
-            ),
-            Ok(("", wanted))
+    #[test]
+    fn parse_error_is_located() {
+        let source = "class A {}\nclass B {";
+        let error = nom::Err::Failure(nom::error::Error::new(
+            &source[source.find('{').unwrap()..],
+            nom::error::ErrorKind::Tag,
+        ));
+        assert_eq!(
+            parse_error(source, error).location(),
+            Some(Location { line: 1, column: 9 })
         );
+
+        let error = parse_error(source, nom::Err::Incomplete(nom::Needed::Unknown));
+        assert_eq!(error.location(), None);
     }
 }
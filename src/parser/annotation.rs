@@ -1,22 +1,35 @@
+use std::fmt;
+
 use nom::{
     branch::alt,
     bytes::complete::{tag, take},
-    combinator::{fail, opt},
+    combinator::{fail, not, opt},
     multi::many0,
-    sequence::delimited,
+    sequence::{delimited, preceded, terminated, tuple},
     IResult, Parser,
 };
 
 use super::{
+    data_type::{generics, DataType},
     identifier::{identifier, Identifier},
-    literal::string_literal_str,
-    whitespace::{some_whitespace, wsc},
+    literal::{string_literal, string_literal_str},
+    position::SourcePosition,
+    utils::split_top_level,
+    whitespace::{doc_comments0, some_whitespace, wsc, wsc_without_doc_comments},
 };
 
 #[derive(Debug, PartialEq, Clone)]
 pub struct Annotation {
     pub name: Identifier,
+    /// Named constructor or prefixed name like `b` in `@a.b()`
+    pub named_constructor: Option<Identifier>,
+    /// Type arguments like `T` in `@A<T>()`
+    pub type_args: Vec<DataType>,
+    /// True if annotation has parameter list like `@a()`
+    pub has_parameters: bool,
     pub parameters: String,
+    /// Position of the `@` sign
+    pub position: SourcePosition,
 }
 
 impl Annotation {
@@ -24,17 +37,151 @@ impl Annotation {
         self.name.name == "freezed" || self.name.name == "Freezed"
     }
 
+    pub fn is_unfreezed_annotation(&self) -> bool {
+        self.name.name == "unfreezed"
+    }
+
+    pub fn is_assert_annotation(&self) -> bool {
+        self.name.name == "Assert"
+    }
+
+    /// Annotation like `@With<T>()` or `@With.fromString('T')`
+    pub fn is_with_annotation(&self) -> bool {
+        self.name.name == "With"
+    }
+
+    /// Annotation like `@Implements<T>()` or `@Implements.fromString('T')`
+    pub fn is_implements_annotation(&self) -> bool {
+        self.name.name == "Implements"
+    }
+
+    pub fn is_deprecated_annotation(&self) -> bool {
+        self.name.name == "Deprecated" || self.name.name == "deprecated"
+    }
+
+    pub fn is_json_key_annotation(&self) -> bool {
+        self.name.name == "JsonKey"
+    }
+
     pub fn is_default_annotation(&self) -> bool {
         self.name.name == "Default"
     }
+
+    /// Annotation like `@DiagnosticsPropertyClass('IntProperty')` which
+    /// selects the Diagnostics property class for a parameter.
+    pub fn is_diagnostics_property_class_annotation(&self) -> bool {
+        self.name.name == "DiagnosticsPropertyClass"
+    }
+
+    /// Split parameters to arguments. Returns `None` if parameters
+    /// are not valid.
+    pub fn arguments(&self) -> Option<Vec<AnnotationArgument>> {
+        let mut arguments = vec![];
+        for argument in split_top_level(&self.parameters, ',')? {
+            let argument = argument.trim();
+            if argument.is_empty() {
+                // Trailing comma is allowed
+                continue;
+            }
+
+            let named: IResult<&str, Identifier> =
+                terminated(identifier, tuple((wsc, tag(":"), not(tag(":")))))(argument);
+
+            let argument = match named {
+                Ok((value, name)) => AnnotationArgument {
+                    name: Some(name),
+                    value: value.trim().to_string(),
+                },
+                Err(_) => AnnotationArgument {
+                    name: None,
+                    value: argument.to_string(),
+                },
+            };
+
+            if argument.value.is_empty() {
+                return None;
+            }
+
+            arguments.push(argument);
+        }
+        Some(arguments)
+    }
+
+    /// Returns the parameters as string if the parameters are only one
+    /// string literal.
+    pub fn string_literal_parameter(&self) -> Option<String> {
+        let (input, _) = wsc(&self.parameters).ok()?;
+        let (input, value) = string_literal(input).ok()?;
+        let (input, _) = wsc(input).ok()?;
+        if input.is_empty() {
+            Some(value)
+        } else {
+            None
+        }
+    }
+}
+
+impl fmt::Display for Annotation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "@{}", self.name)?;
+        if let Some(named_constructor) = &self.named_constructor {
+            write!(f, ".{}", named_constructor)?;
+        }
+        if !self.type_args.is_empty() {
+            let type_args = self
+                .type_args
+                .iter()
+                .map(|t| t.to_string())
+                .collect::<Vec<String>>();
+            write!(f, "<{}>", type_args.join(", "))?;
+        }
+        if self.has_parameters {
+            write!(f, "({})", self.parameters)?;
+        }
+        Ok(())
+    }
+}
+
+/// Annotation argument like `a: 1` or `1`
+#[derive(Debug, PartialEq, Clone)]
+pub struct AnnotationArgument {
+    pub name: Option<Identifier>,
+    /// Expression as source code
+    pub value: String,
+}
+
+impl AnnotationArgument {
+    /// Returns the value if it is a boolean literal.
+    pub fn bool_value(&self) -> Option<bool> {
+        match self.value.as_str() {
+            "true" => Some(true),
+            "false" => Some(false),
+            _ => None,
+        }
+    }
+
+    /// Returns the value if it is a string literal.
+    pub fn string_value(&self) -> Option<String> {
+        match string_literal(&self.value) {
+            Ok(("", value)) => Some(value),
+            _ => None,
+        }
+    }
 }
 
 pub fn annotation(input: &str) -> IResult<&str, Annotation> {
+    let position = SourcePosition::from_remaining_input(input);
     let (input, _) = tag("@")(input)?;
     let (input, _) = wsc(input)?;
-    let (input, identifier) = identifier(input)?;
-    let (input, _) = wsc(input)?;
-    let (input, parameters_str) = if input.starts_with('(') {
+    let (input, name) = identifier(input)?;
+    // Doc comment after annotation belongs to the next declaration
+    let (input, _) = wsc_without_doc_comments(input)?;
+    let (input, named_constructor) = opt(preceded(tuple((tag("."), wsc)), identifier))(input)?;
+    let (input, _) = wsc_without_doc_comments(input)?;
+    let (input, type_args) = opt(generics)(input)?;
+    let (input, _) = wsc_without_doc_comments(input)?;
+    let has_parameters = input.starts_with('(');
+    let (input, parameters_str) = if has_parameters {
         delimited(tag("("), get_parameters_string, tag(")"))(input)?
     } else {
         (input, "")
@@ -43,8 +190,12 @@ pub fn annotation(input: &str) -> IResult<&str, Annotation> {
     Ok((
         input,
         Annotation {
-            name: identifier,
+            name,
+            named_constructor,
+            type_args: type_args.unwrap_or_default(),
+            has_parameters,
             parameters: parameters_str.to_string(),
+            position,
         },
     ))
 }
@@ -58,6 +209,23 @@ pub fn annotations0(input: &str) -> IResult<&str, Vec<Annotation>> {
     many0(wsc_and_annotation)(input)
 }
 
+/// Parse doc comments and annotations before a declaration.
+/// Returns lines of the doc comments and the annotations.
+pub fn documented_annotations0(input: &str) -> IResult<&str, (Vec<String>, Vec<Annotation>)> {
+    let (input, items) = many0(tuple((doc_comments0, annotation)))(input)?;
+    let (input, last_doc_comment) = doc_comments0(input)?;
+
+    let (doc_comments, annotations): (Vec<Vec<String>>, Vec<Annotation>) =
+        items.into_iter().unzip();
+    let doc_comment = doc_comments
+        .into_iter()
+        .flatten()
+        .chain(last_doc_comment)
+        .collect();
+
+    Ok((input, (doc_comment, annotations)))
+}
+
 enum State<'a> {
     WhitespaceOrComment,
     StringLiteal,
@@ -114,7 +282,11 @@ mod tests {
             name: Identifier {
                 name: name.to_string(),
             },
+            named_constructor: None,
+            type_args: vec![],
+            has_parameters: false,
             parameters: "".to_string(),
+            position: SourcePosition::default(),
         }
     }
 
@@ -123,7 +295,19 @@ mod tests {
             name: Identifier {
                 name: name.to_string(),
             },
+            named_constructor: None,
+            type_args: vec![],
+            has_parameters: true,
             parameters: params.to_string(),
+            position: SourcePosition::default(),
+        }
+    }
+
+    /// `annotation` parsed at the start of the remaining input `input`
+    fn at(annotation: Annotation, input: &str) -> Annotation {
+        Annotation {
+            position: SourcePosition::from_remaining_input(input),
+            ..annotation
         }
     }
 
@@ -134,19 +318,66 @@ mod tests {
 
     #[test]
     fn annotation_without_parameters() {
-        assert_eq!(annotation("@a "), Ok(("", a("a"))));
+        assert_eq!(annotation("@a "), Ok(("", at(a("a"), "@a "))));
     }
 
     #[test]
     fn annotation_empty_parameters() {
-        assert_eq!(annotation("@a() "), Ok((" ", a("a"))));
+        assert_eq!(
+            annotation("@a() "),
+            Ok((" ", at(a_params("a", ""), "@a() ")))
+        );
     }
 
     #[test]
     fn annotation_some_parameters() {
         assert_eq!(
             annotation("@a( test, 123 ) "),
-            Ok((" ", a_params("a", " test, 123 ")))
+            Ok((" ", at(a_params("a", " test, 123 "), "@a( test, 123 ) ")))
+        );
+    }
+
+    #[test]
+    fn annotation_type_args() {
+        assert_eq!(
+            annotation("@With < A<B> > () "),
+            Ok((
+                " ",
+                Annotation {
+                    type_args: vec![DataType::Generic {
+                        name: Identifier {
+                            name: "A".to_string()
+                        },
+                        type_args: vec![DataType::Named {
+                            name: Identifier {
+                                name: "B".to_string()
+                            },
+                            nullable: false,
+                        }],
+                        nullable: false,
+                    }],
+                    ..at(a_params("With", ""), "@With < A<B> > () ")
+                }
+            ))
+        );
+    }
+
+    #[test]
+    fn annotation_named_constructor() {
+        assert_eq!(
+            annotation("@Implements.fromString('A<B>') "),
+            Ok((
+                " ",
+                Annotation {
+                    named_constructor: Some(Identifier {
+                        name: "fromString".to_string()
+                    }),
+                    ..at(
+                        a_params("Implements", "'A<B>'"),
+                        "@Implements.fromString('A<B>') "
+                    )
+                }
+            ))
         );
     }
 
@@ -167,20 +398,96 @@ mod tests {
 
     #[test]
     fn annotations0_one_annotation() {
-        assert_eq!(annotations0("@a "), Ok(("", vec![a("a")])));
+        assert_eq!(annotations0("@a "), Ok(("", vec![at(a("a"), "@a ")])));
     }
 
     #[test]
     fn annotations0_multiple_annotations() {
         assert_eq!(
             annotations0("@a@b@c "),
-            Ok(("", vec![a("a"), a("b"), a("c")]))
+            Ok((
+                "",
+                vec![
+                    at(a("a"), "@a@b@c "),
+                    at(a("b"), "@b@c "),
+                    at(a("c"), "@c ")
+                ]
+            ))
         );
     }
 
     #[test]
     fn annotations0_start_with_whitespace() {
-        assert_eq!(annotations0(" @a "), Ok(("", vec![a("a")])));
+        assert_eq!(annotations0(" @a "), Ok(("", vec![at(a("a"), "@a ")])));
+    }
+
+    #[test]
+    fn documented_annotations0_collects_doc_comments() {
+        assert_eq!(
+            documented_annotations0("/// a\n @b /// c\n d"),
+            Ok((
+                "d",
+                (
+                    vec!["/// a".to_string(), "/// c".to_string()],
+                    vec![at(a("b"), "@b /// c\n d")]
+                )
+            ))
+        );
+    }
+
+    #[test]
+    fn display_annotation() {
+        let (_, parsed) = annotation("@A . b < C > ( 'd' )").unwrap();
+        assert_eq!(parsed.to_string(), "@A.b<C>( 'd' )");
+        assert_eq!(a("deprecated").to_string(), "@deprecated");
+    }
+
+    #[test]
+    fn arguments_named_and_positional() {
+        assert_eq!(
+            a_params("a", " 'x, y', b: f(1, 2), c: [1, 2],").arguments(),
+            Some(vec![
+                AnnotationArgument {
+                    name: None,
+                    value: "'x, y'".to_string(),
+                },
+                AnnotationArgument {
+                    name: Some(Identifier {
+                        name: "b".to_string()
+                    }),
+                    value: "f(1, 2)".to_string(),
+                },
+                AnnotationArgument {
+                    name: Some(Identifier {
+                        name: "c".to_string()
+                    }),
+                    value: "[1, 2]".to_string(),
+                },
+            ])
+        );
+    }
+
+    #[test]
+    fn arguments_empty() {
+        assert_eq!(a("a").arguments(), Some(vec![]));
+    }
+
+    #[test]
+    fn arguments_named_argument_without_value() {
+        assert_eq!(a_params("a", "b: ").arguments(), None);
+    }
+
+    #[test]
+    fn string_literal_parameter_works() {
+        assert_eq!(
+            a_params("a", " 'b' ").string_literal_parameter(),
+            Some("b".to_string())
+        );
+    }
+
+    #[test]
+    fn string_literal_parameter_other_content_after_string() {
+        assert_eq!(a_params("a", "'b', c").string_literal_parameter(), None);
     }
 
     #[test]
@@ -1,6 +1,7 @@
 pub mod constructor;
 pub mod items;
-pub mod named_parameters;
+pub mod member;
+pub mod parameters;
 
 use nom::{bytes::complete::tag, combinator::opt, sequence::delimited, IResult};
 
@@ -8,8 +9,10 @@ use self::items::{class_item_info, ClassItemInfo};
 
 use super::{
     annotation::{annotations0, Annotation},
+    data_type::{data_type, DataType},
     identifier::{identifier, Identifier},
     keyword::{class_keyword, implements_keyword, with_keyword},
+    position::SourcePosition,
     utils::comma_separated1,
     whitespace::wsc,
 };
@@ -18,16 +21,23 @@ use super::{
 pub struct ClassDefinition {
     pub annotations: Vec<Annotation>,
     pub name: Identifier,
-    pub mixin_types: Vec<Identifier>,
+    pub mixin_types: Vec<DataType>,
+    /// Position of the first mixin type after `with`
+    pub mixin_types_position: Option<SourcePosition>,
     pub implemented_types: Vec<Identifier>,
     pub item_info: ClassItemInfo,
+    pub name_position: SourcePosition,
+    /// Position of the opening brace of the class body
+    pub body_position: SourcePosition,
+    /// Position after the closing brace of the class body
+    pub end_position: SourcePosition,
 }
 
 impl ClassDefinition {
     pub fn contains_freezed_annotation(&self) -> bool {
-        self.annotations
-            .iter()
-            .any(|annotation| annotation.is_freezed_annotation())
+        self.annotations.iter().any(|annotation| {
+            annotation.is_freezed_annotation() || annotation.is_unfreezed_annotation()
+        })
     }
 }
 
@@ -36,17 +46,20 @@ pub fn class(input: &str) -> IResult<&str, ClassDefinition> {
     let (input, _) = wsc(input)?;
     let (input, _) = class_keyword(input)?;
     let (input, _) = wsc(input)?;
+    let name_position = SourcePosition::from_remaining_input(input);
     let (input, name) = identifier(input)?;
     let (input, _) = wsc(input)?;
 
-    let (input, mixin_types) = parse_mixin_types(input)?;
+    let (input, (mixin_types, mixin_types_position)) = parse_mixin_types(input)?;
     let (input, _) = wsc(input)?;
 
     let (input, implemented_types) = parse_implemented_types(input)?;
     let (input, _) = wsc(input)?;
 
+    let body_position = SourcePosition::from_remaining_input(input);
     let (input, item_info) =
         delimited(tag("{"), |input| class_item_info(&name, input), tag("}"))(input)?;
+    let end_position = SourcePosition::from_remaining_input(input);
 
     Ok((
         input,
@@ -54,21 +67,28 @@ pub fn class(input: &str) -> IResult<&str, ClassDefinition> {
             annotations,
             name,
             mixin_types,
+            mixin_types_position,
             implemented_types,
             item_info,
+            name_position,
+            body_position,
+            end_position,
         },
     ))
 }
 
-pub fn parse_mixin_types(input: &str) -> IResult<&str, Vec<Identifier>> {
+/// Parse the `with` clause. Returns the types and the position of the
+/// first type.
+pub fn parse_mixin_types(input: &str) -> IResult<&str, (Vec<DataType>, Option<SourcePosition>)> {
     let (input, _) = wsc(input)?;
     let (input, with_detected) = opt(with_keyword)(input)?;
     if with_detected.is_some() {
         let (input, _) = wsc(input)?;
-        let (input, identifiers) = comma_separated1(identifier, input)?;
-        Ok((input, identifiers))
+        let position = SourcePosition::from_remaining_input(input);
+        let (input, types) = comma_separated1(data_type, input)?;
+        Ok((input, (types, Some(position))))
     } else {
-        Ok((input, vec![]))
+        Ok((input, (vec![], None)))
     }
 }
 
@@ -88,104 +108,89 @@ pub fn parse_implemented_types(input: &str) -> IResult<&str, Vec<Identifier>> {
 mod tests {
     use tests::constructor::{FactoryConstructor, PrivateConstructor};
 
-    use crate::parser::data_type::DataType;
+    use crate::parser::position::SourcePosition;
 
-    use self::{items::ClassItem, named_parameters::NamedParameter};
+    use self::{
+        items::ClassItem,
+        member::ClassMember,
+        parameters::{Parameter, ParameterKind},
+    };
 
     use super::*;
 
-    fn c(name: &str) -> ClassDefinition {
+    /// Class `name` parsed from all of `source`
+    fn c(source: &str, name: &str) -> ClassDefinition {
         ClassDefinition {
             annotations: vec![],
             name: Identifier {
                 name: name.to_string(),
             },
             mixin_types: vec![],
+            mixin_types_position: None,
             implemented_types: vec![],
             item_info: ClassItemInfo { items: vec![] },
+            name_position: SourcePosition::after(source, "class "),
+            body_position: SourcePosition::of(source, "{"),
+            end_position: SourcePosition::from_remaining_input(""),
         }
     }
 
-    fn annotated_c(annotations: &[&str], name: &str) -> ClassDefinition {
+    fn annotated_c(source: &str, annotations: &[&str], name: &str) -> ClassDefinition {
         let annotations = annotations
             .iter()
             .map(|a| Annotation {
                 name: Identifier {
                     name: a.to_string(),
                 },
+                named_constructor: None,
+                type_args: vec![],
+                has_parameters: false,
                 parameters: "".to_string(),
+                position: SourcePosition::of(source, &format!("@{}", a)),
             })
             .collect();
         ClassDefinition {
             annotations,
-            name: Identifier {
-                name: name.to_string(),
-            },
-            mixin_types: vec![],
-            implemented_types: vec![],
-            item_info: ClassItemInfo { items: vec![] },
+            ..c(source, name)
         }
     }
 
-    fn c_with_mixins(name: &str, with_types: &[&str]) -> ClassDefinition {
-        let with_types = with_types
-            .iter()
-            .map(|a| Identifier {
-                name: a.to_string(),
-            })
-            .collect();
-        ClassDefinition {
-            annotations: vec![],
+    fn mixin(name: &str) -> DataType {
+        DataType::Named {
             name: Identifier {
                 name: name.to_string(),
             },
-            mixin_types: with_types,
-            implemented_types: vec![],
-            item_info: ClassItemInfo { items: vec![] },
+            nullable: false,
         }
     }
 
-    fn c_with_items(name: &str, items: Vec<ClassItem>) -> ClassDefinition {
+    fn c_with_mixins(source: &str, name: &str, with_types: &[&str]) -> ClassDefinition {
         ClassDefinition {
-            annotations: vec![],
-            name: Identifier {
-                name: name.to_string(),
-            },
-            mixin_types: vec![],
-            implemented_types: vec![],
-            item_info: ClassItemInfo { items },
+            mixin_types: with_types.iter().map(|a| mixin(a)).collect(),
+            mixin_types_position: with_types
+                .first()
+                .map(|_| SourcePosition::after(source, "with ")),
+            ..c(source, name)
         }
     }
 
-    fn c_with_implements(name: &str, implements_types: &[&str]) -> ClassDefinition {
-        let implements_types = implements_types
-            .iter()
-            .map(|a| Identifier {
-                name: a.to_string(),
-            })
-            .collect();
+    fn c_with_items(source: &str, name: &str, items: Vec<ClassItem>) -> ClassDefinition {
         ClassDefinition {
-            annotations: vec![],
-            name: Identifier {
-                name: name.to_string(),
-            },
-            mixin_types: vec![],
-            implemented_types: implements_types,
-            item_info: ClassItemInfo { items: vec![] },
+            item_info: ClassItemInfo { items },
+            ..c(source, name)
         }
     }
 
+    fn c_with_implements(source: &str, name: &str, implements_types: &[&str]) -> ClassDefinition {
+        c_with_mixins_and_implements(source, name, &[], implements_types)
+    }
+
     fn c_with_mixins_and_implements(
+        source: &str,
         name: &str,
         with_types: &[&str],
         implements_types: &[&str],
     ) -> ClassDefinition {
-        let with_types = with_types
-            .iter()
-            .map(|a| Identifier {
-                name: a.to_string(),
-            })
-            .collect();
         let implements_types = implements_types
             .iter()
             .map(|a| Identifier {
@@ -193,26 +198,23 @@ mod tests {
             })
             .collect();
         ClassDefinition {
-            annotations: vec![],
-            name: Identifier {
-                name: name.to_string(),
-            },
-            mixin_types: with_types,
             implemented_types: implements_types,
-            item_info: ClassItemInfo { items: vec![] },
+            ..c_with_mixins(source, name, with_types)
         }
     }
 
-    fn named_parameter(class_name: &str, name: &str) -> NamedParameter {
-        NamedParameter {
+    fn named_parameter(class_name: &str, name: &str) -> Parameter {
+        Parameter {
+            doc_comment: vec![],
             annotations: vec![],
+            kind: ParameterKind::Named,
             required: false,
-            parameter_type: DataType {
+            is_final: false,
+            parameter_type: DataType::Named {
                 name: Identifier {
                     name: class_name.to_string(),
                 },
                 nullable: false,
-                type_args: vec![],
             },
             name: Identifier {
                 name: name.to_string(),
@@ -220,32 +222,69 @@ mod tests {
         }
     }
 
-    fn factory_constructor(params: Vec<NamedParameter>) -> ClassItem {
+    fn factory_constructor(params: Vec<Parameter>) -> ClassItem {
         ClassItem::FactoryConstructor(FactoryConstructor {
+            doc_comment: vec![],
+            annotations: vec![],
             params,
             is_const: false,
+            redirect_target: Identifier {
+                name: "_".to_string(),
+            },
         })
     }
 
     #[test]
     fn class_with_inner_scopes() {
+        let source = "class A { { } }";
         assert_eq!(
-            class("class A { { } }"),
+            class(source),
             // TOOD: Should class parsing be more strict?
-            Ok((" }", c("A")))
+            Ok((
+                " }",
+                ClassDefinition {
+                    end_position: SourcePosition::from_remaining_input(" }"),
+                    ..c(source, "A")
+                }
+            ))
         );
     }
 
+    #[test]
+    fn class_positions() {
+        let source = "@a\nclass A with B {\n  int get b => 1;\n}\nclass C {}";
+        let (_, parsed) = class(source).unwrap();
+        assert_eq!(
+            parsed.annotations[0].position.line_and_column(source),
+            (1, 1)
+        );
+        assert_eq!(parsed.name_position.line_and_column(source), (2, 7));
+        assert_eq!(
+            parsed.mixin_types_position.unwrap().line_and_column(source),
+            (2, 14)
+        );
+        assert_eq!(parsed.body_position.line_and_column(source), (2, 16));
+        assert_eq!(parsed.end_position.line_and_column(source), (4, 2));
+        match &parsed.item_info.items[..] {
+            [ClassItem::Member(member)] => {
+                assert_eq!(member.position.line_and_column(source), (3, 3))
+            }
+            items => panic!("unexpected items {:?}", items),
+        }
+    }
+
     #[test]
     fn class_with_annotation() {
-        assert_eq!(class("@a class A {}"), Ok(("", annotated_c(&["a"], "A"))));
+        let source = "@a class A {}";
+        assert_eq!(class(source), Ok(("", annotated_c(source, &["a"], "A"))));
     }
 
     #[test]
     fn class_with_multiple_annotations() {
+        let source = "@a @b @c class A {}";
         assert_eq!(
-            class("@a @b @c class A {}"),
-            Ok(("", annotated_c(&["a", "b", "c"], "A")))
+            class(source),
+            Ok(("", annotated_c(source, &["a", "b", "c"], "A")))
         );
     }
 
@@ -261,27 +300,28 @@ mod tests {
 
     #[test]
     fn class_and_with_keyword_and_one_type() {
-        assert_eq!(
-            class("class A with B {}"),
-            Ok(("", c_with_mixins("A", &["B"])))
-        );
+        let source = "class A with B {}";
+        assert_eq!(class(source), Ok(("", c_with_mixins(source, "A", &["B"]))));
     }
 
     #[test]
     fn class_and_with_keyword_and_two_types() {
+        let source = "class A with B, C {}";
         assert_eq!(
-            class("class A with B, C {}"),
-            Ok(("", c_with_mixins("A", &["B", "C"])))
+            class(source),
+            Ok(("", c_with_mixins(source, "A", &["B", "C"])))
         );
     }
 
     #[test]
     fn class_and_private_constructor() {
+        let source = "class A { A._(); }";
         assert_eq!(
-            class("class A { A._(); }"),
+            class(source),
             Ok((
                 "",
                 c_with_items(
+                    source,
                     "A",
                     vec![ClassItem::PrivateConstructor(PrivateConstructor {
                         is_const: false
@@ -292,21 +332,47 @@ mod tests {
     }
 
     #[test]
-    fn class_and_getter_and_private_constructor() {
+    fn class_and_static_member_and_private_constructor() {
+        let source = "class A { static const b = 1;\n  A._(); }";
         assert_eq!(
-            class(
-                "class A {
+            class(source),
+            Ok((
+                "",
+                c_with_items(
+                    source,
+                    "A",
+                    vec![
+                        ClassItem::Member(ClassMember {
+                            is_instance_member: false,
+                            position: SourcePosition::of(source, "static"),
+                        }),
+                        ClassItem::PrivateConstructor(PrivateConstructor { is_const: false }),
+                    ],
+                )
+            ))
+        );
+    }
+
+    #[test]
+    fn class_and_getter_and_private_constructor() {
+        let source = "class A {
                     int get number => 1;
                     A._();
-                }"
-            ),
+                }";
+        assert_eq!(
+            class(source),
             Ok((
                 "",
                 c_with_items(
+                    source,
                     "A",
-                    vec![ClassItem::PrivateConstructor(PrivateConstructor {
-                        is_const: false
-                    }),],
+                    vec![
+                        ClassItem::Member(ClassMember {
+                            is_instance_member: true,
+                            position: SourcePosition::of(source, "int get"),
+                        }),
+                        ClassItem::PrivateConstructor(PrivateConstructor { is_const: false }),
+                    ],
                 )
             ))
         );
@@ -314,17 +380,17 @@ mod tests {
 
     #[test]
     fn class_and_factory_constructors() {
-        assert_eq!(
-            class(
-                "class A {
+        let source = "class A {
                     factory A({
                         B b,
                     }) = _ ;
-                }"
-            ),
+                }";
+        assert_eq!(
+            class(source),
             Ok((
                 "",
                 c_with_items(
+                    source,
                     "A",
                     vec![factory_constructor(vec![named_parameter("B", "b"),]),],
                 )
@@ -334,33 +400,43 @@ mod tests {
 
     #[test]
     fn class_and_implements_keyword_and_one_type() {
+        let source = "class A implements B {}";
         assert_eq!(
-            class("class A implements B {}"),
-            Ok(("", c_with_implements("A", &["B"])))
+            class(source),
+            Ok(("", c_with_implements(source, "A", &["B"])))
         );
     }
 
     #[test]
     fn class_and_implements_keyword_and_two_types() {
+        let source = "class A implements B, C {}";
         assert_eq!(
-            class("class A implements B, C {}"),
-            Ok(("", c_with_implements("A", &["B", "C"])))
+            class(source),
+            Ok(("", c_with_implements(source, "A", &["B", "C"])))
         );
     }
 
     #[test]
     fn class_with_mixin_and_implements_one_type() {
+        let source = "class A with M implements B {}";
         assert_eq!(
-            class("class A with M implements B {}"),
-            Ok(("", c_with_mixins_and_implements("A", &["M"], &["B"])))
+            class(source),
+            Ok((
+                "",
+                c_with_mixins_and_implements(source, "A", &["M"], &["B"])
+            ))
         );
     }
 
     #[test]
     fn class_with_mixin_and_implements_two_types() {
+        let source = "class A with M implements B, C {}";
         assert_eq!(
-            class("class A with M implements B, C {}"),
-            Ok(("", c_with_mixins_and_implements("A", &["M"], &["B", "C"])))
+            class(source),
+            Ok((
+                "",
+                c_with_mixins_and_implements(source, "A", &["M"], &["B", "C"])
+            ))
         );
     }
 }
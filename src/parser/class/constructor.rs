@@ -1,17 +1,13 @@
-use nom::{
-    bytes::complete::tag,
-    combinator::opt,
-    sequence::{delimited, preceded, tuple},
-    IResult,
-};
+use nom::{bytes::complete::tag, combinator::opt, IResult};
 
 use crate::parser::{
+    annotation::{documented_annotations0, Annotation},
     identifier::{identifier, Identifier},
     keyword::{const_keyword, factory_keyword},
     whitespace::wsc,
 };
 
-use super::named_parameters::{named_parameters0, NamedParameter};
+use super::parameters::{parameter_list, Parameter};
 
 /// Constructor like `ClassName._();`
 #[derive(Debug, PartialEq, Clone)]
@@ -45,16 +41,24 @@ pub fn private_constructor<'a>(
     ))
 }
 
+/// Constructor like `factory ClassName({int a}) = _ClassName;`
 #[derive(Debug, PartialEq, Clone)]
 pub struct FactoryConstructor {
-    pub params: Vec<NamedParameter>,
+    /// Doc comment lines like `/// Text`
+    pub doc_comment: Vec<String>,
+    pub annotations: Vec<Annotation>,
+    pub params: Vec<Parameter>,
     pub is_const: bool,
+    /// Class name after `=`
+    pub redirect_target: Identifier,
 }
 
 pub fn factory_constructor<'a>(
     class_name: &Identifier,
     input: &'a str,
 ) -> IResult<&'a str, FactoryConstructor> {
+    let (input, (doc_comment, annotations)) = documented_annotations0(input)?;
+    let (input, _) = wsc(input)?;
     let (input, is_const) = opt(const_keyword)(input)?;
     let (input, _) = wsc(input)?;
     let (input, _) = factory_keyword(input)?;
@@ -62,28 +66,23 @@ pub fn factory_constructor<'a>(
     let (input, _) = tag(class_name.name.as_bytes())(input)?;
     let (input, _) = wsc(input)?;
 
-    let (input, params) = delimited(
-        tag("("),
-        delimited(
-            preceded(wsc, tag("{")),
-            preceded(wsc, named_parameters0),
-            preceded(tuple((wsc, tag("}"))), wsc),
-        ),
-        tag(")"),
-    )(input)?;
+    let (input, params) = parameter_list(input)?;
 
     let (input, _) = wsc(input)?;
     let (input, _) = tag("=")(input)?;
     let (input, _) = wsc(input)?;
-    let (input, _) = identifier(input)?;
+    let (input, redirect_target) = identifier(input)?;
     let (input, _) = wsc(input)?;
     let (input, _) = tag(";")(input)?;
 
     Ok((
         input,
         FactoryConstructor {
+            doc_comment,
+            annotations,
             params,
             is_const: is_const.is_some(),
+            redirect_target,
         },
     ))
 }
@@ -91,7 +90,10 @@ pub fn factory_constructor<'a>(
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::parser::{data_type::DataType, identifier::Identifier};
+    use crate::parser::position::SourcePosition;
+    use crate::parser::{
+        class::parameters::ParameterKind, data_type::DataType, identifier::Identifier,
+    };
 
     fn identifier(name: &str) -> Identifier {
         Identifier {
@@ -99,14 +101,24 @@ mod tests {
         }
     }
 
-    fn named_parameter(class_name: &str, name: &str) -> NamedParameter {
-        NamedParameter {
+    fn named_parameter(class_name: &str, name: &str) -> Parameter {
+        parameter_with_kind(ParameterKind::Named, class_name, name)
+    }
+
+    fn positional_parameter(class_name: &str, name: &str) -> Parameter {
+        parameter_with_kind(ParameterKind::Positional, class_name, name)
+    }
+
+    fn parameter_with_kind(kind: ParameterKind, class_name: &str, name: &str) -> Parameter {
+        Parameter {
+            doc_comment: vec![],
             annotations: vec![],
+            kind,
             required: false,
-            parameter_type: DataType {
+            is_final: false,
+            parameter_type: DataType::Named {
                 name: identifier(class_name),
                 nullable: false,
-                type_args: vec![],
             },
             name: Identifier {
                 name: name.to_string(),
@@ -118,17 +130,23 @@ mod tests {
         PrivateConstructor { is_const }
     }
 
-    fn f(params: impl AsRef<[NamedParameter]>) -> FactoryConstructor {
+    fn f(params: impl AsRef<[Parameter]>) -> FactoryConstructor {
         FactoryConstructor {
+            doc_comment: vec![],
+            annotations: vec![],
             params: params.as_ref().to_vec(),
             is_const: false,
+            redirect_target: identifier("_"),
         }
     }
 
-    fn f_with_is_const(params: impl AsRef<[NamedParameter]>, is_const: bool) -> FactoryConstructor {
+    fn f_with_is_const(params: impl AsRef<[Parameter]>, is_const: bool) -> FactoryConstructor {
         FactoryConstructor {
+            doc_comment: vec![],
+            annotations: vec![],
             params: params.as_ref().to_vec(),
             is_const,
+            redirect_target: identifier("_"),
         }
     }
 
@@ -195,4 +213,84 @@ mod tests {
             Ok(("", f_with_is_const([], true)))
         );
     }
+
+    #[test]
+    fn factory_constructor_positional_parameters() {
+        assert_eq!(
+            factory_constructor(&identifier("A"), "factory A(B b, C c) = _ ;"),
+            Ok((
+                "",
+                f([
+                    positional_parameter("B", "b"),
+                    positional_parameter("C", "c"),
+                ])
+            ))
+        );
+    }
+
+    #[test]
+    fn factory_constructor_redirect_target() {
+        assert_eq!(
+            factory_constructor(&identifier("A"), "factory A() = _A;"),
+            Ok((
+                "",
+                FactoryConstructor {
+                    doc_comment: vec![],
+                    annotations: vec![],
+                    params: vec![],
+                    is_const: false,
+                    redirect_target: identifier("_A"),
+                }
+            ))
+        );
+    }
+
+    #[test]
+    fn factory_constructor_with_annotations() {
+        let source = "@Assert('a') @b const factory A() = _A;";
+        assert_eq!(
+            factory_constructor(&identifier("A"), source),
+            Ok((
+                "",
+                FactoryConstructor {
+                    doc_comment: vec![],
+                    annotations: vec![
+                        Annotation {
+                            name: identifier("Assert"),
+                            named_constructor: None,
+                            type_args: vec![],
+                            has_parameters: true,
+                            parameters: "'a'".to_string(),
+                            position: SourcePosition::of(source, "@Assert"),
+                        },
+                        Annotation {
+                            name: identifier("b"),
+                            named_constructor: None,
+                            type_args: vec![],
+                            has_parameters: false,
+                            parameters: "".to_string(),
+                            position: SourcePosition::of(source, "@b"),
+                        },
+                    ],
+                    params: vec![],
+                    is_const: true,
+                    redirect_target: identifier("_A"),
+                }
+            ))
+        );
+    }
+
+    #[test]
+    fn factory_constructor_with_doc_comment() {
+        assert_eq!(
+            factory_constructor(&identifier("A"), "/// a\n  factory A() = _;"),
+            Ok((
+                "",
+                FactoryConstructor {
+                    doc_comment: vec!["/// a".to_string()],
+                    ..f([])
+                }
+            ))
+        );
+    }
 }
@@ -1,9 +1,14 @@
-use nom::{branch::alt, IResult, Parser};
+use nom::{branch::alt, sequence::preceded, IResult, Parser};
 
-use crate::parser::{identifier::Identifier, utils::item_parser};
+use crate::parser::{
+    identifier::Identifier, utils::item_parser, whitespace::wsc_without_doc_comments,
+};
 
-use super::constructor::{
-    factory_constructor, private_constructor, FactoryConstructor, PrivateConstructor,
+use super::{
+    constructor::{
+        factory_constructor, private_constructor, FactoryConstructor, PrivateConstructor,
+    },
+    member::{class_member, ClassMember},
 };
 
 #[derive(Debug, PartialEq)]
@@ -15,6 +20,7 @@ pub struct ClassItemInfo {
 pub enum ClassItem {
     PrivateConstructor(PrivateConstructor),
     FactoryConstructor(FactoryConstructor),
+    Member(ClassMember),
 }
 
 pub fn class_item_info<'a>(
@@ -23,10 +29,16 @@ pub fn class_item_info<'a>(
 ) -> IResult<&'a str, ClassItemInfo> {
     item_parser(
         |input| input.starts_with('}'),
-        alt((
-            (|input| private_constructor(class_name, input)).map(ClassItem::PrivateConstructor),
-            (|input| factory_constructor(class_name, input)).map(ClassItem::FactoryConstructor),
-        )),
+        // Members are tried last, so whitespace is skipped before all
+        // the alternatives
+        preceded(
+            wsc_without_doc_comments,
+            alt((
+                (|input| private_constructor(class_name, input)).map(ClassItem::PrivateConstructor),
+                (|input| factory_constructor(class_name, input)).map(ClassItem::FactoryConstructor),
+                (|input| class_member(class_name, input)).map(ClassItem::Member),
+            )),
+        ),
         input,
     )
     .map(|(input, items)| (input, ClassItemInfo { items }))
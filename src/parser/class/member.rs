@@ -0,0 +1,204 @@
+use nom::{
+    branch::alt,
+    bytes::complete::tag,
+    combinator::opt,
+    error::{Error, ErrorKind},
+    sequence::tuple,
+    IResult, Parser,
+};
+
+use crate::parser::{
+    annotation::annotations0,
+    identifier::Identifier,
+    keyword::{const_keyword, factory_keyword, static_keyword},
+    literal::string_literal_str,
+    position::SourcePosition,
+    whitespace::{some_whitespace, wsc},
+};
+
+/// Declaration other than the constructors which the generated code is
+/// based on, like a getter, a method, a field or another constructor.
+/// Only the kind of the declaration is recorded.
+#[derive(Debug, PartialEq, Clone)]
+pub struct ClassMember {
+    /// False for static members and constructors
+    pub is_instance_member: bool,
+    pub position: SourcePosition,
+}
+
+pub fn class_member<'a>(class_name: &Identifier, input: &'a str) -> IResult<&'a str, ClassMember> {
+    let (input, _) = annotations0(input)?;
+    let (input, _) = wsc(input)?;
+    if !input.starts_with(|c: char| c.is_alphabetic() || c == '_' || c == '$') {
+        return Err(nom::Err::Error(Error::new(input, ErrorKind::Alpha)));
+    }
+
+    let position = SourcePosition::from_remaining_input(input);
+    let is_instance_member = static_member_or_constructor(class_name, input).is_err();
+    let (input, _) = declaration(input)?;
+
+    Ok((
+        input,
+        ClassMember {
+            is_instance_member,
+            position,
+        },
+    ))
+}
+
+/// Start of `static ...`, `factory X...` or `X(...)` and `X.name(...)`
+fn static_member_or_constructor<'a>(
+    class_name: &Identifier,
+    input: &'a str,
+) -> IResult<&'a str, ()> {
+    let (input, _) = opt(tuple((const_keyword, wsc)))(input)?;
+    alt((
+        static_keyword.map(|_| ()),
+        factory_keyword.map(|_| ()),
+        tuple((
+            tag(class_name.name.as_str()),
+            wsc,
+            alt((tag("("), tag("."))),
+        ))
+        .map(|_| ()),
+    ))(input)
+}
+
+/// Skip a declaration which ends with `;` or with the closing brace of
+/// a block body. Fails if the declaration ends at the closing brace of
+/// the class body.
+fn declaration(input: &str) -> IResult<&str, ()> {
+    let mut depth: usize = 0;
+    let mut block_body = false;
+    // `=` of an initializer or `=>` of an expression body was found, so
+    // braces belong to an expression
+    let mut expression = false;
+    let mut previous = ' ';
+    let mut current_input = input;
+
+    while let Some(c) = current_input.chars().next() {
+        if let Ok((next, _)) = alt((some_whitespace, string_literal_str))(current_input) {
+            current_input = next;
+            continue;
+        }
+
+        let next = &current_input[c.len_utf8()..];
+        match c {
+            '{' if depth == 0 && !expression => {
+                block_body = true;
+                depth += 1;
+            }
+            '(' | '[' | '{' => depth += 1,
+            ')' | ']' | '}' => {
+                let Some(new_depth) = depth.checked_sub(1) else {
+                    break;
+                };
+                depth = new_depth;
+                if depth == 0 && block_body {
+                    return Ok((next, ()));
+                }
+            }
+            ';' if depth == 0 => return Ok((next, ())),
+            // Operators like `==` and `<=` are not initializers
+            '=' if depth == 0 && !matches!(previous, '=' | '<' | '>' | '!') => {
+                expression = next.starts_with('>') || !next.starts_with('=');
+            }
+            _ => (),
+        }
+        previous = c;
+        current_input = next;
+    }
+
+    Err(nom::Err::Error(Error::new(current_input, ErrorKind::Eof)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn member(input: &str) -> IResult<&str, ClassMember> {
+        class_member(
+            &Identifier {
+                name: "A".to_string(),
+            },
+            input,
+        )
+    }
+
+    /// Member parsed from the start of `input`
+    fn instance_member(input: &str, is_instance_member: bool) -> ClassMember {
+        ClassMember {
+            is_instance_member,
+            position: SourcePosition::from_remaining_input(input),
+        }
+    }
+
+    #[test]
+    fn getter_with_expression_body() {
+        assert_eq!(
+            member("int get b => c.length; }"),
+            Ok((" }", instance_member("int get b => c.length; }", true)))
+        );
+    }
+
+    #[test]
+    fn method_with_block_body() {
+        let declaration = "String toString() { if (a) { return '}'; } return ''; } }";
+        // Position is after the annotations
+        assert_eq!(
+            member(&format!("@override {}", declaration)),
+            Ok((" }", instance_member(declaration, true)))
+        );
+    }
+
+    #[test]
+    fn field_with_initializer() {
+        assert_eq!(
+            member("late final b = {1, 2}; }"),
+            Ok((" }", instance_member("late final b = {1, 2}; }", true)))
+        );
+    }
+
+    #[test]
+    fn equality_operator() {
+        assert_eq!(
+            member("bool operator ==(Object other) { return true; } }"),
+            Ok((
+                " }",
+                instance_member("bool operator ==(Object other) { return true; } }", true)
+            ))
+        );
+    }
+
+    #[test]
+    fn static_member() {
+        assert_eq!(
+            member("static const b = 1; }"),
+            Ok((" }", instance_member("static const b = 1; }", false)))
+        );
+    }
+
+    #[test]
+    fn other_constructors() {
+        assert_eq!(
+            member("factory A.fromJson(Map<String, dynamic> json) => _$AFromJson(json); }"),
+            Ok((
+                " }",
+                instance_member(
+                    "factory A.fromJson(Map<String, dynamic> json) => _$AFromJson(json); }",
+                    false
+                )
+            ))
+        );
+        assert_eq!(
+            member("const A.b() : this(); }"),
+            Ok((" }", instance_member("const A.b() : this(); }", false)))
+        );
+    }
+
+    #[test]
+    fn unterminated_member_makes_failure() {
+        assert!(member("int get b }").is_err());
+        assert!(member("{ }").is_err());
+    }
+}
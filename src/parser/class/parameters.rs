@@ -0,0 +1,375 @@
+use nom::{
+    branch::alt,
+    bytes::complete::tag,
+    combinator::opt,
+    sequence::{delimited, preceded},
+    IResult, Parser,
+};
+
+use crate::parser::{
+    annotation::{documented_annotations0, Annotation},
+    data_type::{data_type, DataType},
+    identifier::{identifier, Identifier},
+    keyword::{final_keyword, required_keyword},
+    utils::documented_comma_separated0,
+    whitespace::wsc,
+};
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum ParameterKind {
+    /// Parameter like `A(int a)`
+    Positional,
+    /// Parameter like `A([int? a])`
+    OptionalPositional,
+    /// Parameter like `A({int? a})`
+    Named,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct Parameter {
+    /// Doc comment lines like `/// Text`
+    pub doc_comment: Vec<String>,
+    pub annotations: Vec<Annotation>,
+    pub kind: ParameterKind,
+    pub required: bool,
+    pub is_final: bool,
+    pub parameter_type: DataType,
+    pub name: Identifier,
+}
+
+impl Parameter {
+    pub fn default_annotation(&self) -> Option<String> {
+        self.annotations.iter().find_map(|annotation| {
+            if annotation.is_default_annotation() {
+                Some(annotation.parameters.clone())
+            } else {
+                None
+            }
+        })
+    }
+
+    pub fn diagnostics_property_class_annotation(&self) -> Option<&Annotation> {
+        self.annotations
+            .iter()
+            .find(|annotation| annotation.is_diagnostics_property_class_annotation())
+    }
+
+    /// Annotation like `@Deprecated('message')` or `@deprecated`
+    pub fn deprecated_annotation(&self) -> Option<&Annotation> {
+        self.annotations
+            .iter()
+            .find(|annotation| annotation.is_deprecated_annotation())
+    }
+
+    pub fn json_key_annotation(&self) -> Option<&Annotation> {
+        self.annotations
+            .iter()
+            .find(|annotation| annotation.is_json_key_annotation())
+    }
+
+    /// `@Deprecated` and `@JsonKey` annotations, which are copied onto
+    /// the getters, fields and copyWith parameters of the parameter
+    pub fn member_annotations(&self) -> impl Iterator<Item = &Annotation> {
+        self.deprecated_annotation()
+            .into_iter()
+            .chain(self.json_key_annotation())
+    }
+
+    pub fn is_named(&self) -> bool {
+        self.kind == ParameterKind::Named
+    }
+}
+
+pub fn named_parameter(input: &str) -> IResult<&str, Parameter> {
+    let (input, (doc_comment, annotations)) = documented_annotations0(input)?;
+    let (input, _) = wsc(input)?;
+    let (input, required) = opt(required_keyword)(input)?;
+    let (input, _) = wsc(input)?;
+    let (input, is_final) = opt(final_keyword)(input)?;
+    let (input, _) = wsc(input)?;
+    let (input, parameter_type) = data_type(input)?;
+    let (input, _) = wsc(input)?;
+    let (input, name) = identifier(input)?;
+
+    Ok((
+        input,
+        Parameter {
+            doc_comment,
+            annotations,
+            kind: ParameterKind::Named,
+            required: required.is_some(),
+            is_final: is_final.is_some(),
+            parameter_type,
+            name,
+        },
+    ))
+}
+
+pub fn named_parameters0(input: &str) -> IResult<&str, Vec<Parameter>> {
+    let (input, params) = documented_comma_separated0(named_parameter, input)?;
+    let (input, _) = wsc(input)?;
+    let (input, _) = opt(tag(","))(input)?;
+    Ok((input, params))
+}
+
+pub fn positional_parameter(input: &str) -> IResult<&str, Parameter> {
+    let (input, (doc_comment, annotations)) = documented_annotations0(input)?;
+    let (input, _) = wsc(input)?;
+    let (input, is_final) = opt(final_keyword)(input)?;
+    let (input, _) = wsc(input)?;
+    let (input, parameter_type) = data_type(input)?;
+    let (input, _) = wsc(input)?;
+    let (input, name) = identifier(input)?;
+
+    Ok((
+        input,
+        Parameter {
+            doc_comment,
+            annotations,
+            kind: ParameterKind::Positional,
+            required: false,
+            is_final: is_final.is_some(),
+            parameter_type,
+            name,
+        },
+    ))
+}
+
+pub fn positional_parameters0(input: &str) -> IResult<&str, Vec<Parameter>> {
+    let (input, params) = documented_comma_separated0(positional_parameter, input)?;
+    let (input, _) = wsc(input)?;
+    let (input, _) = opt(tag(","))(input)?;
+    Ok((input, params))
+}
+
+fn optional_positional_parameters0(input: &str) -> IResult<&str, Vec<Parameter>> {
+    positional_parameters0
+        .map(|params| {
+            params
+                .into_iter()
+                .map(|param| Parameter {
+                    kind: ParameterKind::OptionalPositional,
+                    ..param
+                })
+                .collect()
+        })
+        .parse(input)
+}
+
+/// Parse parameter list like `(int a, [int? b])` or `(int a, {int? b})`.
+pub fn parameter_list(input: &str) -> IResult<&str, Vec<Parameter>> {
+    let (input, _) = tag("(")(input)?;
+    let (input, mut params) = positional_parameters0(input)?;
+    let (input, _) = wsc(input)?;
+    let (input, optional_params) = opt(alt((
+        delimited(
+            tag("["),
+            optional_positional_parameters0,
+            preceded(wsc, tag("]")),
+        ),
+        delimited(tag("{"), named_parameters0, preceded(wsc, tag("}"))),
+    )))(input)?;
+    let (input, _) = wsc(input)?;
+    let (input, _) = tag(")")(input)?;
+
+    params.extend(optional_params.unwrap_or_default());
+
+    Ok((input, params))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::identifier::Identifier;
+    use crate::parser::position::SourcePosition;
+
+    /// Annotation `name` parsed at the first `@name` in `source`
+    fn a(source: &str, name: &str) -> Annotation {
+        Annotation {
+            name: Identifier {
+                name: name.to_string(),
+            },
+            named_constructor: None,
+            type_args: vec![],
+            has_parameters: false,
+            parameters: "".to_string(),
+            position: SourcePosition::of(source, &format!("@{}", name)),
+        }
+    }
+
+    fn c_name(name: &str) -> Identifier {
+        Identifier {
+            name: name.to_string(),
+        }
+    }
+
+    fn parameter_with_kind(kind: ParameterKind, class_name: &str, name: &str) -> Parameter {
+        Parameter {
+            doc_comment: vec![],
+            annotations: vec![],
+            kind,
+            required: false,
+            is_final: false,
+            parameter_type: DataType::Named {
+                name: c_name(class_name),
+                nullable: false,
+            },
+            name: Identifier {
+                name: name.to_string(),
+            },
+        }
+    }
+
+    fn parameter(class_name: &str, name: &str) -> Parameter {
+        parameter_with_kind(ParameterKind::Named, class_name, name)
+    }
+
+    fn positional(class_name: &str, name: &str) -> Parameter {
+        parameter_with_kind(ParameterKind::Positional, class_name, name)
+    }
+
+    fn optional_positional(class_name: &str, name: &str) -> Parameter {
+        parameter_with_kind(ParameterKind::OptionalPositional, class_name, name)
+    }
+
+    #[test]
+    fn named_parameter_parsed_correctly() {
+        let source = "@a @b @c  required A a";
+        assert_eq!(
+            named_parameter(source),
+            Ok((
+                "",
+                Parameter {
+                    doc_comment: vec![],
+                    annotations: vec![a(source, "a"), a(source, "b"), a(source, "c")],
+                    kind: ParameterKind::Named,
+                    required: true,
+                    is_final: false,
+                    parameter_type: DataType::Named {
+                        name: c_name("A"),
+                        nullable: false,
+                    },
+                    name: Identifier {
+                        name: "a".to_string()
+                    },
+                }
+            ))
+        );
+    }
+
+    #[test]
+    fn list_of_named_paramters_prevents_comma_in_beginning() {
+        assert!(named_parameters0("  , A a").is_err())
+    }
+
+    #[test]
+    fn empty_parameter_list() {
+        assert_eq!(named_parameters0(" "), Ok(("", vec![])));
+    }
+
+    #[test]
+    fn one_paramters_allow_comma_in_end() {
+        assert_eq!(
+            named_parameters0("A a, "),
+            Ok((" ", vec![parameter("A", "a"),]))
+        );
+    }
+
+    #[test]
+    fn one_paramters_trailing_comma_consumed_even_if_whitespace_before_it() {
+        assert_eq!(
+            named_parameters0("A a   , "),
+            Ok((" ", vec![parameter("A", "a"),]))
+        );
+    }
+
+    #[test]
+    fn one_named_paramters_no_comma_in_end_works() {
+        assert_eq!(
+            named_parameters0("A a"),
+            Ok(("", vec![parameter("A", "a"),]))
+        );
+    }
+
+    #[test]
+    fn two_named_paramters_allow_comma_in_end() {
+        assert_eq!(
+            named_parameters0("A a, B b, "),
+            Ok((" ", vec![parameter("A", "a"), parameter("B", "b"),]))
+        );
+    }
+
+    #[test]
+    fn two_named_paramters_no_comma_in_end_works() {
+        assert_eq!(
+            named_parameters0("A a, B b"),
+            Ok(("", vec![parameter("A", "a"), parameter("B", "b"),]))
+        );
+    }
+
+    #[test]
+    fn named_parameter_with_final() {
+        assert_eq!(
+            named_parameter("required final A a"),
+            Ok((
+                "",
+                Parameter {
+                    required: true,
+                    is_final: true,
+                    ..parameter("A", "a")
+                }
+            ))
+        );
+    }
+
+    #[test]
+    fn positional_parameter_with_final() {
+        assert_eq!(
+            positional_parameter("final A a"),
+            Ok((
+                "",
+                Parameter {
+                    is_final: true,
+                    ..positional("A", "a")
+                }
+            ))
+        );
+    }
+
+    #[test]
+    fn parameter_list_empty() {
+        assert_eq!(parameter_list("( )"), Ok(("", vec![])));
+    }
+
+    #[test]
+    fn parameter_list_positional() {
+        assert_eq!(
+            parameter_list("(A a, B b,)"),
+            Ok(("", vec![positional("A", "a"), positional("B", "b")]))
+        );
+    }
+
+    #[test]
+    fn parameter_list_positional_and_optional_positional() {
+        assert_eq!(
+            parameter_list("(A a, [B b, ])"),
+            Ok((
+                "",
+                vec![positional("A", "a"), optional_positional("B", "b")]
+            ))
+        );
+    }
+
+    #[test]
+    fn parameter_list_positional_and_named() {
+        assert_eq!(
+            parameter_list("(A a, { B b })"),
+            Ok(("", vec![positional("A", "a"), parameter("B", "b")]))
+        );
+    }
+
+    #[test]
+    fn parameter_list_optional_and_named_parameters_not_allowed() {
+        assert!(parameter_list("([A a], { B b })").is_err());
+    }
+}
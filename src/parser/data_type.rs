@@ -1,66 +1,383 @@
 use core::fmt;
 
-use nom::{bytes::complete::tag, combinator::opt, IResult};
+use nom::{
+    branch::alt,
+    bytes::complete::tag,
+    combinator::{opt, verify},
+    sequence::{delimited, preceded, tuple},
+    IResult, Parser,
+};
 
-use super::{identifier::identifier, utils::comma_separated1, whitespace::wsc, Identifier};
+use super::{
+    identifier::identifier,
+    keyword::{extends_keyword, required_keyword},
+    utils::{comma_separated0, comma_separated1},
+    whitespace::wsc,
+    Identifier,
+};
+
+const DYNAMIC_TYPE_NAME: &str = "dynamic";
+const VOID_TYPE_NAME: &str = "void";
+const NULL_TYPE_NAME: &str = "Null";
+const FUNCTION_TYPE_NAME: &str = "Function";
+const FUTURE_OR_TYPE_NAME: &str = "FutureOr";
+const COLLECTION_TYPE_NAMES: &[&str] = &["List", "Set", "Map"];
 
 #[derive(Debug, PartialEq, Clone)]
-pub struct DataType {
-    pub(in crate::parser) name: Identifier,
-    pub nullable: bool,
-    pub type_args: Vec<DataType>,
+pub enum DataType {
+    /// Type like `int` or `String?`
+    Named { name: Identifier, nullable: bool },
+    /// Type with type arguments like `List<int>?`
+    Generic {
+        name: Identifier,
+        type_args: Vec<DataType>,
+        nullable: bool,
+    },
+    /// Type from an import with a prefix like `m.LatLng`
+    Prefixed {
+        prefix: Identifier,
+        data_type: Box<DataType>,
+    },
+    /// Function type like `void Function(String)?`
+    Function(FunctionType),
+    /// Record type like `(int, {String name})`
+    Record(RecordType),
+    /// `dynamic`
+    Dynamic,
+    /// `void`
+    Void,
 }
 
 impl DataType {
+    /// Returns true if `null` is assignable to the type.
+    pub fn is_nullable(&self) -> bool {
+        match self {
+            Self::Named { name, nullable } => *nullable || name.as_str() == NULL_TYPE_NAME,
+            Self::Generic {
+                name,
+                type_args,
+                nullable,
+            } => {
+                // FutureOr<T> is nullable if T is nullable
+                *nullable
+                    || (name.as_str() == FUTURE_OR_TYPE_NAME
+                        && type_args.iter().any(|t| t.is_nullable()))
+            }
+            Self::Prefixed { data_type, .. } => data_type.is_nullable(),
+            Self::Function(function) => function.nullable,
+            Self::Record(record) => record.nullable,
+            Self::Dynamic | Self::Void => true,
+        }
+    }
+
+    /// Returns true if the type is `List`, `Set` or `Map` from
+    /// `dart:core`, which are compared by identity with `==`.
+    pub fn is_collection(&self) -> bool {
+        match self {
+            Self::Named { name, .. } | Self::Generic { name, .. } => {
+                COLLECTION_TYPE_NAMES.contains(&name.as_str())
+            }
+            _ => false,
+        }
+    }
+
     pub fn to_nullable(&self) -> DataType {
-        DataType {
-            name: self.name.clone(),
-            nullable: true,
-            type_args: self.type_args.clone(),
+        let mut data_type = self.clone();
+        match &mut data_type {
+            Self::Named { nullable, .. } | Self::Generic { nullable, .. } => *nullable = true,
+            Self::Prefixed { data_type, .. } => **data_type = data_type.to_nullable(),
+            Self::Function(function) => function.nullable = true,
+            Self::Record(record) => record.nullable = true,
+            // Already nullable and adding `?` is not allowed
+            Self::Dynamic | Self::Void => (),
+        }
+        data_type
+    }
+
+    pub fn to_non_nullable(&self) -> DataType {
+        let mut data_type = self.clone();
+        match &mut data_type {
+            Self::Named { nullable, .. } | Self::Generic { nullable, .. } => *nullable = false,
+            Self::Prefixed { data_type, .. } => **data_type = data_type.to_non_nullable(),
+            Self::Function(function) => function.nullable = false,
+            Self::Record(record) => record.nullable = false,
+            Self::Dynamic | Self::Void => (),
+        }
+        data_type
+    }
+
+    /// Type name without prefix, type arguments and nullability.
+    ///
+    /// Returns `None` for function and record types.
+    pub fn base_name(&self) -> Option<&str> {
+        match self {
+            Self::Named { name, .. } | Self::Generic { name, .. } => Some(name.as_str()),
+            Self::Prefixed { data_type, .. } => data_type.base_name(),
+            Self::Function(_) | Self::Record(_) => None,
+            Self::Dynamic => Some(DYNAMIC_TYPE_NAME),
+            Self::Void => Some(VOID_TYPE_NAME),
+        }
+    }
+
+    pub fn type_args(&self) -> &[DataType] {
+        match self {
+            Self::Generic { type_args, .. } => type_args,
+            Self::Prefixed { data_type, .. } => data_type.type_args(),
+            _ => &[],
         }
     }
 }
 
 impl fmt::Display for DataType {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        let generics = if self.type_args.is_empty() {
-            "".to_string()
+        match self {
+            Self::Named { name, nullable } => {
+                write!(f, "{}{}", name, nullable_marker(*nullable))
+            }
+            Self::Generic {
+                name,
+                type_args,
+                nullable,
+            } => write!(
+                f,
+                "{}<{}>{}",
+                name,
+                join(type_args),
+                nullable_marker(*nullable)
+            ),
+            Self::Prefixed { prefix, data_type } => write!(f, "{}.{}", prefix, data_type),
+            Self::Function(function) => write!(f, "{}", function),
+            Self::Record(record) => write!(f, "{}", record),
+            Self::Dynamic => write!(f, "{}", DYNAMIC_TYPE_NAME),
+            Self::Void => write!(f, "{}", VOID_TYPE_NAME),
+        }
+    }
+}
+
+/// Function type like `R Function<T>(A a, [B b])`
+#[derive(Debug, PartialEq, Clone)]
+pub struct FunctionType {
+    pub return_type: Option<Box<DataType>>,
+    pub type_params: Vec<TypeParameter>,
+    pub positional_params: Vec<FunctionTypeParameter>,
+    pub optional_positional_params: Vec<FunctionTypeParameter>,
+    pub named_params: Vec<FunctionTypeParameter>,
+    pub nullable: bool,
+}
+
+impl fmt::Display for FunctionType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if let Some(return_type) = &self.return_type {
+            write!(f, "{} ", return_type)?;
+        }
+
+        write!(f, "{}", FUNCTION_TYPE_NAME)?;
+
+        if !self.type_params.is_empty() {
+            write!(f, "<{}>", join(&self.type_params))?;
+        }
+
+        let mut params = self
+            .positional_params
+            .iter()
+            .map(|p| p.to_string())
+            .collect::<Vec<String>>();
+
+        if !self.optional_positional_params.is_empty() {
+            params.push(format!("[{}]", join(&self.optional_positional_params)));
+        }
+
+        if !self.named_params.is_empty() {
+            params.push(format!("{{{}}}", join(&self.named_params)));
+        }
+
+        write!(
+            f,
+            "({}){}",
+            params.join(", "),
+            nullable_marker(self.nullable)
+        )
+    }
+}
+
+/// Parameter of a function type. Parameter name is optional
+/// for positional parameters.
+#[derive(Debug, PartialEq, Clone)]
+pub struct FunctionTypeParameter {
+    pub required: bool,
+    pub data_type: DataType,
+    pub name: Option<Identifier>,
+}
+
+impl fmt::Display for FunctionTypeParameter {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.required {
+            write!(f, "required ")?;
+        }
+
+        write!(f, "{}", self.data_type)?;
+
+        if let Some(name) = &self.name {
+            write!(f, " {}", name)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Type parameter like `T` or `T extends Object`
+#[derive(Debug, PartialEq, Clone)]
+pub struct TypeParameter {
+    pub name: Identifier,
+    pub bound: Option<DataType>,
+}
+
+impl fmt::Display for TypeParameter {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if let Some(bound) = &self.bound {
+            write!(f, "{} extends {}", self.name, bound)
         } else {
-            format!(
-                "<{}>",
-                self.type_args
-                    .iter()
-                    .map(|t| t.to_string())
-                    .collect::<Vec<String>>()
-                    .join(", ")
-            )
+            write!(f, "{}", self.name)
+        }
+    }
+}
+
+/// Record type like `(int, String name, {bool flag})`
+#[derive(Debug, PartialEq, Clone)]
+pub struct RecordType {
+    pub positional_fields: Vec<RecordField>,
+    pub named_fields: Vec<RecordField>,
+    pub nullable: bool,
+}
+
+impl fmt::Display for RecordType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut fields = self
+            .positional_fields
+            .iter()
+            .map(|p| p.to_string())
+            .collect::<Vec<String>>();
+
+        if !self.named_fields.is_empty() {
+            fields.push(format!("{{{}}}", join(&self.named_fields)));
+        }
+
+        // Record with only one positional field requires a trailing comma
+        let trailing_comma = if self.positional_fields.len() == 1 && self.named_fields.is_empty() {
+            ","
+        } else {
+            ""
         };
 
-        if self.nullable {
-            write!(f, "{}{}?", self.name, generics)
+        write!(
+            f,
+            "({}{}){}",
+            fields.join(", "),
+            trailing_comma,
+            nullable_marker(self.nullable)
+        )
+    }
+}
+
+/// Record field. Field name is optional for positional fields.
+#[derive(Debug, PartialEq, Clone)]
+pub struct RecordField {
+    pub data_type: DataType,
+    pub name: Option<Identifier>,
+}
+
+impl fmt::Display for RecordField {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if let Some(name) = &self.name {
+            write!(f, "{} {}", self.data_type, name)
         } else {
-            write!(f, "{}{}", self.name, generics)
+            write!(f, "{}", self.data_type)
         }
     }
 }
 
+fn nullable_marker(nullable: bool) -> &'static str {
+    if nullable {
+        "?"
+    } else {
+        ""
+    }
+}
+
+fn join(items: &[impl fmt::Display]) -> String {
+    items
+        .iter()
+        .map(|t| t.to_string())
+        .collect::<Vec<String>>()
+        .join(", ")
+}
+
 pub fn data_type(input: &str) -> IResult<&str, DataType> {
     let (input, _) = wsc(input)?;
-    let (input, name) = identifier(input)?;
+    let (mut input, mut current_type) = alt((
+        record_type.map(DataType::Record),
+        (|input| function_type(None, input)).map(DataType::Function),
+        named_data_type,
+    ))(input)?;
+
+    // Function types can be chained like `void Function() Function()`
+    loop {
+        let (next_input, _) = wsc(input)?;
+        match function_type(Some(current_type.clone()), next_input) {
+            Ok((next_input, function)) => {
+                input = next_input;
+                current_type = DataType::Function(function);
+            }
+            Err(nom::Err::Error(_)) => break,
+            Err(e) => return Err(e),
+        }
+    }
+
+    let (input, _) = wsc(input)?;
+
+    Ok((input, current_type))
+}
+
+fn named_data_type(input: &str) -> IResult<&str, DataType> {
+    let (input, first_name) = identifier(input)?;
+
+    match first_name.as_str() {
+        DYNAMIC_TYPE_NAME => return Ok((input, DataType::Dynamic)),
+        VOID_TYPE_NAME => return Ok((input, DataType::Void)),
+        _ => (),
+    }
+
+    let (input, _) = wsc(input)?;
+    let (input, second_name) = opt(preceded(tuple((tag("."), wsc)), identifier))(input)?;
     let (input, _) = wsc(input)?;
     let (input, type_args) = opt(generics)(input)?;
     let (input, _) = wsc(input)?;
     let (input, nullable) = opt(tag("?"))(input)?;
-    let (input, _) = wsc(input)?;
 
-    Ok((
-        input,
-        DataType {
+    let (prefix, name) = match second_name {
+        Some(name) => (Some(first_name), name),
+        None => (None, first_name),
+    };
+
+    let nullable = nullable.is_some();
+    let data_type = match type_args {
+        Some(type_args) => DataType::Generic {
             name,
-            nullable: nullable.is_some(),
-            type_args: type_args.unwrap_or_default(),
+            type_args,
+            nullable,
         },
-    ))
+        None => DataType::Named { name, nullable },
+    };
+
+    let data_type = match prefix {
+        Some(prefix) => DataType::Prefixed {
+            prefix,
+            data_type: Box::new(data_type),
+        },
+        None => data_type,
+    };
+
+    Ok((input, data_type))
 }
 
 pub fn generics(input: &str) -> IResult<&str, Vec<DataType>> {
@@ -73,20 +390,180 @@ pub fn generics(input: &str) -> IResult<&str, Vec<DataType>> {
     Ok((input, types))
 }
 
+fn function_type(return_type: Option<DataType>, input: &str) -> IResult<&str, FunctionType> {
+    let (input, _) = verify(identifier, |name| name.as_str() == FUNCTION_TYPE_NAME)(input)?;
+    let (input, _) = wsc(input)?;
+    let (input, type_params) = opt(type_parameters)(input)?;
+    let (input, _) = wsc(input)?;
+    let (input, _) = tag("(")(input)?;
+
+    let (input, positional_params) = comma_separated0(positional_function_type_parameter, input)?;
+    let (input, _) = trailing_comma(input)?;
+    let (input, optional_positional_params) = opt(delimited(
+        tag("["),
+        |input| comma_separated1(positional_function_type_parameter, input),
+        preceded(trailing_comma, tag("]")),
+    ))(input)?;
+    let (input, named_params) = if optional_positional_params.is_none() {
+        opt(delimited(
+            tag("{"),
+            |input| comma_separated1(named_function_type_parameter, input),
+            preceded(trailing_comma, tag("}")),
+        ))(input)?
+    } else {
+        (input, None)
+    };
+
+    let (input, _) = wsc(input)?;
+    let (input, _) = tag(")")(input)?;
+    let (input, _) = wsc(input)?;
+    let (input, nullable) = opt(tag("?"))(input)?;
+
+    Ok((
+        input,
+        FunctionType {
+            return_type: return_type.map(Box::new),
+            type_params: type_params.unwrap_or_default(),
+            positional_params,
+            optional_positional_params: optional_positional_params.unwrap_or_default(),
+            named_params: named_params.unwrap_or_default(),
+            nullable: nullable.is_some(),
+        },
+    ))
+}
+
+fn type_parameters(input: &str) -> IResult<&str, Vec<TypeParameter>> {
+    delimited(
+        tag("<"),
+        |input| comma_separated1(type_parameter, input),
+        preceded(wsc, tag(">")),
+    )(input)
+}
+
+fn type_parameter(input: &str) -> IResult<&str, TypeParameter> {
+    let (input, name) = identifier(input)?;
+    let (input, _) = wsc(input)?;
+    let (input, bound) = opt(preceded(extends_keyword, data_type))(input)?;
+
+    Ok((input, TypeParameter { name, bound }))
+}
+
+fn positional_function_type_parameter(input: &str) -> IResult<&str, FunctionTypeParameter> {
+    let (input, data_type) = data_type(input)?;
+    let (input, name) = opt(identifier)(input)?;
+
+    Ok((
+        input,
+        FunctionTypeParameter {
+            required: false,
+            data_type,
+            name,
+        },
+    ))
+}
+
+fn named_function_type_parameter(input: &str) -> IResult<&str, FunctionTypeParameter> {
+    let (input, required) = opt(required_keyword)(input)?;
+    let (input, _) = wsc(input)?;
+    let (input, data_type) = data_type(input)?;
+    let (input, name) = identifier(input)?;
+
+    Ok((
+        input,
+        FunctionTypeParameter {
+            required: required.is_some(),
+            data_type,
+            name: Some(name),
+        },
+    ))
+}
+
+fn record_type(input: &str) -> IResult<&str, RecordType> {
+    let (input, _) = tag("(")(input)?;
+
+    let (input, positional_fields) = comma_separated0(positional_record_field, input)?;
+    let (input, _) = trailing_comma(input)?;
+    let (input, named_fields) = opt(delimited(
+        tag("{"),
+        |input| comma_separated1(named_record_field, input),
+        preceded(trailing_comma, tag("}")),
+    ))(input)?;
+
+    let (input, _) = wsc(input)?;
+    let (input, _) = tag(")")(input)?;
+    let (input, _) = wsc(input)?;
+    let (input, nullable) = opt(tag("?"))(input)?;
+
+    Ok((
+        input,
+        RecordType {
+            positional_fields,
+            named_fields: named_fields.unwrap_or_default(),
+            nullable: nullable.is_some(),
+        },
+    ))
+}
+
+fn positional_record_field(input: &str) -> IResult<&str, RecordField> {
+    let (input, data_type) = data_type(input)?;
+    let (input, name) = opt(identifier)(input)?;
+
+    Ok((input, RecordField { data_type, name }))
+}
+
+fn named_record_field(input: &str) -> IResult<&str, RecordField> {
+    let (input, data_type) = data_type(input)?;
+    let (input, name) = identifier(input)?;
+
+    Ok((
+        input,
+        RecordField {
+            data_type,
+            name: Some(name),
+        },
+    ))
+}
+
+/// Remove optional comma and whitespace around it
+fn trailing_comma(input: &str) -> IResult<&str, ()> {
+    let (input, _) = wsc(input)?;
+    let (input, _) = opt(tag(","))(input)?;
+    let (input, _) = wsc(input)?;
+    Ok((input, ()))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    fn id(name: &str) -> Identifier {
+        Identifier {
+            name: name.to_string(),
+        }
+    }
+
     fn dtype(name: &str, nullable: bool, type_args: impl AsRef<[DataType]>) -> DataType {
-        DataType {
-            name: Identifier {
-                name: name.to_string(),
-            },
-            nullable,
-            type_args: type_args.as_ref().to_vec(),
+        let type_args = type_args.as_ref().to_vec();
+        if type_args.is_empty() {
+            DataType::Named {
+                name: id(name),
+                nullable,
+            }
+        } else {
+            DataType::Generic {
+                name: id(name),
+                type_args,
+                nullable,
+            }
         }
     }
 
+    fn parse_and_display(input: &str) -> String {
+        let (rest, data_type) = data_type(input).unwrap();
+        assert_eq!(rest, "");
+        data_type.to_string()
+    }
+
     #[test]
     fn data_type_non_nullable() {
         assert_eq!(data_type("Test "), Ok(("", dtype("Test", false, []))));
@@ -132,4 +609,125 @@ mod tests {
             Ok(("", dtype("Test", false, [arg1, arg2])))
         );
     }
+
+    #[test]
+    fn data_type_dynamic_and_void() {
+        assert_eq!(data_type("dynamic "), Ok(("", DataType::Dynamic)));
+        assert_eq!(
+            data_type("FutureOr<void>"),
+            Ok(("", dtype("FutureOr", false, [DataType::Void])))
+        );
+    }
+
+    #[test]
+    fn data_type_prefixed() {
+        assert_eq!(
+            data_type("m . LatLng? "),
+            Ok((
+                "",
+                DataType::Prefixed {
+                    prefix: id("m"),
+                    data_type: Box::new(dtype("LatLng", true, [])),
+                }
+            ))
+        );
+    }
+
+    #[test]
+    fn data_type_function_type() {
+        assert_eq!(
+            data_type("void Function(String)? onTap"),
+            Ok((
+                "onTap",
+                DataType::Function(FunctionType {
+                    return_type: Some(Box::new(DataType::Void)),
+                    type_params: vec![],
+                    positional_params: vec![FunctionTypeParameter {
+                        required: false,
+                        data_type: dtype("String", false, []),
+                        name: None,
+                    }],
+                    optional_positional_params: vec![],
+                    named_params: vec![],
+                    nullable: true,
+                })
+            ))
+        );
+    }
+
+    #[test]
+    fn data_type_function_class_is_named_type() {
+        assert_eq!(
+            data_type("Function? f"),
+            Ok(("f", dtype("Function", true, [])))
+        );
+    }
+
+    #[test]
+    fn data_type_record_type() {
+        assert_eq!(
+            data_type("(int, {String name}) pair"),
+            Ok((
+                "pair",
+                DataType::Record(RecordType {
+                    positional_fields: vec![RecordField {
+                        data_type: dtype("int", false, []),
+                        name: None,
+                    }],
+                    named_fields: vec![RecordField {
+                        data_type: dtype("String", false, []),
+                        name: Some(id("name")),
+                    }],
+                    nullable: false,
+                })
+            ))
+        );
+    }
+
+    #[test]
+    fn data_type_display_roundtrip() {
+        for input in [
+            "int",
+            "List<Map<String, int?>>?",
+            "m.LatLng",
+            "m.Box<int>?",
+            "FutureOr<void>",
+            "dynamic",
+            "Function",
+            "void Function(String)?",
+            "int? Function<T extends Object>(T a, [int? b])",
+            "void Function({required String name, int count})",
+            "void Function() Function(int)",
+            "(int, {String name})",
+            "(int,)?",
+            "(int x, int y)",
+            "()",
+            "List<(int, String)?>",
+        ] {
+            assert_eq!(parse_and_display(input), input);
+        }
+    }
+
+    #[test]
+    fn data_type_nullability() {
+        let nullable = |input: &str| data_type(input).unwrap().1.is_nullable();
+        assert!(!nullable("int"));
+        assert!(nullable("int?"));
+        assert!(nullable("dynamic"));
+        assert!(nullable("Null"));
+        assert!(nullable("m.A?"));
+        assert!(!nullable("void Function(int?)"));
+        assert!(nullable("(int,)?"));
+        assert!(nullable("FutureOr<void>"));
+        assert!(!nullable("FutureOr<int>"));
+    }
+
+    #[test]
+    fn data_type_to_nullable() {
+        let to_nullable = |input: &str| data_type(input).unwrap().1.to_nullable().to_string();
+        assert_eq!(to_nullable("m.A<int>"), "m.A<int>?");
+        assert_eq!(to_nullable("void Function()"), "void Function()?");
+        assert_eq!(to_nullable("(int, int)"), "(int, int)?");
+        assert_eq!(to_nullable("dynamic"), "dynamic");
+    }
 }
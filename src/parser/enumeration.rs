@@ -0,0 +1,45 @@
+use nom::IResult;
+
+use super::{
+    annotation::annotations0,
+    identifier::{identifier, Identifier},
+    keyword::enum_keyword,
+    whitespace::wsc,
+};
+
+/// Enum declaration. Only the name is parsed and the enum body
+/// is skipped.
+#[derive(Debug, PartialEq)]
+pub struct EnumDefinition {
+    pub name: Identifier,
+}
+
+pub fn enum_definition(input: &str) -> IResult<&str, EnumDefinition> {
+    let (input, _) = annotations0(input)?;
+    let (input, _) = wsc(input)?;
+    let (input, _) = enum_keyword(input)?;
+    let (input, _) = wsc(input)?;
+    let (input, name) = identifier(input)?;
+
+    Ok((input, EnumDefinition { name }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn enum_definition_with_annotation() {
+        assert_eq!(
+            enum_definition("@a enum A { b, c }"),
+            Ok((
+                " { b, c }",
+                EnumDefinition {
+                    name: Identifier {
+                        name: "A".to_string()
+                    }
+                }
+            ))
+        );
+    }
+}
@@ -0,0 +1,87 @@
+use nom::{bytes::complete::tag, multi::many0, IResult};
+
+use super::{
+    import::{combinator, import_configuration, Combinator, ImportConfiguration},
+    keyword::export_keyword,
+    literal::string_literal,
+    position::SourcePosition,
+    whitespace::wsc,
+};
+
+#[derive(Debug, PartialEq)]
+pub struct ExportStatement {
+    pub path: String,
+    /// Configurable URIs like `if (dart.library.io) 'io.dart'`
+    pub configurations: Vec<ImportConfiguration>,
+    pub combinators: Vec<Combinator>,
+    /// Position after the terminating semicolon
+    pub end_position: SourcePosition,
+}
+
+impl ExportStatement {
+    /// Returns true if `name` from the exported library is exported.
+    pub fn exports(&self, name: &str) -> bool {
+        self.combinators
+            .iter()
+            .all(|combinator| combinator.allows(name))
+    }
+}
+
+pub fn export_statement(input: &str) -> IResult<&str, ExportStatement> {
+    let (input, _) = export_keyword(input)?;
+    let (input, _) = wsc(input)?;
+    let (input, path) = string_literal(input)?;
+    let (input, _) = wsc(input)?;
+    let (input, configurations) = many0(import_configuration)(input)?;
+    let (input, _) = wsc(input)?;
+    let (input, combinators) = many0(combinator)(input)?;
+    let (input, _) = wsc(input)?;
+    let (input, _) = tag(";")(input)?;
+    let end_position = SourcePosition::from_remaining_input(input);
+
+    Ok((
+        input,
+        ExportStatement {
+            path,
+            configurations,
+            combinators,
+            end_position,
+        },
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::identifier::Identifier;
+
+    #[test]
+    fn export_statement_other_than_export_statement() {
+        assert!(export_statement("import 'a';").is_err());
+    }
+
+    #[test]
+    fn export_statement_with_combinators() {
+        assert_eq!(
+            export_statement("export 'a' hide B;"),
+            Ok((
+                "",
+                ExportStatement {
+                    path: "a".to_string(),
+                    configurations: vec![],
+                    combinators: vec![Combinator::Hide(vec![Identifier {
+                        name: "B".to_string()
+                    }])],
+                    end_position: SourcePosition::from_remaining_input(""),
+                }
+            ))
+        );
+    }
+
+    #[test]
+    fn exports_handles_combinators() {
+        let (_, export) = export_statement("export 'a' show A;").unwrap();
+        assert!(export.exports("A"));
+        assert!(!export.exports("B"));
+    }
+}
@@ -0,0 +1,244 @@
+use nom::{
+    bytes::complete::tag,
+    combinator::opt,
+    multi::{many1, separated_list1},
+    sequence::{terminated, tuple},
+    IResult,
+};
+
+use super::{
+    data_type::generics,
+    identifier::{identifier, Identifier},
+    keyword::{const_keyword, new_keyword},
+    literal::string_literal_str,
+    utils::split_top_level,
+    whitespace::wsc,
+};
+
+/// Kind of an expression like the value in `@Default(...)`
+#[derive(Debug, PartialEq, Clone)]
+pub enum ExpressionKind {
+    Null,
+    Bool,
+    Int,
+    Double,
+    String,
+    /// List literal like `[1]` or `<int>[]`
+    List,
+    /// Set literal like `{1}` or `<int>{}`
+    Set,
+    /// Map literal like `{}` or `{'a': 1}`
+    Map,
+    /// Constructor or function invocation like `A()`, `A.b()` or `a()`
+    Invocation {
+        const_keyword: bool,
+        new_keyword: bool,
+        name: Vec<Identifier>,
+    },
+    /// Name like `a` or `A.b`
+    Name(Vec<Identifier>),
+    /// Other expressions like `1 + 2`
+    Other,
+}
+
+impl ExpressionKind {
+    /// Returns true for null, bool, number, string and collection literals.
+    pub fn is_literal(&self) -> bool {
+        !matches!(self, Self::Invocation { .. } | Self::Name(_) | Self::Other)
+    }
+
+    /// Description like `a list literal` for error messages.
+    pub fn description(&self) -> &'static str {
+        match self {
+            Self::Null => "null",
+            Self::Bool => "a bool literal",
+            Self::Int => "an int literal",
+            Self::Double => "a double literal",
+            Self::String => "a string literal",
+            Self::List => "a list literal",
+            Self::Set => "a set literal",
+            Self::Map => "a map literal",
+            Self::Invocation { .. } => "an invocation",
+            Self::Name(_) => "a name",
+            Self::Other => "an expression",
+        }
+    }
+}
+
+/// Classify expression. Leading and trailing whitespace
+/// and comments are ignored.
+pub fn expression_kind(input: &str) -> ExpressionKind {
+    expression_kind_with_keywords(input).unwrap_or(ExpressionKind::Other)
+}
+
+fn expression_kind_with_keywords(input: &str) -> Option<ExpressionKind> {
+    let (input, _) = wsc(input).ok()?;
+    let input = input.trim_end();
+
+    match input {
+        "null" => return Some(ExpressionKind::Null),
+        "true" | "false" => return Some(ExpressionKind::Bool),
+        _ => (),
+    }
+
+    if let Some(kind) = number_kind(input) {
+        return Some(kind);
+    }
+
+    let adjacent_strings: IResult<&str, _> = many1(terminated(string_literal_str, wsc))(input);
+    if let Ok(("", _)) = adjacent_strings {
+        return Some(ExpressionKind::String);
+    }
+
+    let (input, const_keyword) = opt(terminated(const_keyword, wsc))(input).ok()?;
+    let (input, new_keyword) = opt(terminated(new_keyword, wsc))(input).ok()?;
+    let (input, type_args) = opt(terminated(generics, wsc))(input).ok()?;
+
+    if let Some(inner) = bracketed(input, '[', ']') {
+        return if new_keyword.is_none() && type_args.is_none_or(|t| t.len() == 1) {
+            split_top_level(inner, ',').map(|_| ExpressionKind::List)
+        } else {
+            None
+        };
+    }
+
+    if let Some(inner) = bracketed(input, '{', '}') {
+        if new_keyword.is_some() {
+            return None;
+        }
+        let elements = split_top_level(inner, ',')?;
+        return match type_args.map(|t| t.len()) {
+            Some(1) => Some(ExpressionKind::Set),
+            Some(2) => Some(ExpressionKind::Map),
+            Some(_) => None,
+            None if inner.trim().is_empty() => Some(ExpressionKind::Map),
+            None if split_top_level(elements[0], ':')?.len() > 1 => Some(ExpressionKind::Map),
+            None => Some(ExpressionKind::Set),
+        };
+    }
+
+    if type_args.is_some() {
+        return None;
+    }
+
+    let (input, name) = separated_list1(tuple((wsc, tag("."), wsc)), identifier)(input).ok()?;
+    let (input, _) = wsc(input).ok()?;
+    let (input, _) = opt(terminated(generics, wsc))(input).ok()?;
+
+    if input.is_empty() && const_keyword.is_none() && new_keyword.is_none() {
+        return Some(ExpressionKind::Name(name));
+    }
+
+    let arguments = bracketed(input, '(', ')')?;
+    split_top_level(arguments, ',')?;
+
+    Some(ExpressionKind::Invocation {
+        const_keyword: const_keyword.is_some(),
+        new_keyword: new_keyword.is_some(),
+        name,
+    })
+}
+
+/// Returns the input between `start` and `end` if the input
+/// starts with `start` and ends with `end`.
+fn bracketed(input: &str, start: char, end: char) -> Option<&str> {
+    input.strip_prefix(start)?.strip_suffix(end)
+}
+
+fn number_kind(input: &str) -> Option<ExpressionKind> {
+    let number = input.strip_prefix('-').unwrap_or(input).replace('_', "");
+
+    if let Some(hex) = number
+        .strip_prefix("0x")
+        .or_else(|| number.strip_prefix("0X"))
+    {
+        return (!hex.is_empty() && hex.chars().all(|c| c.is_ascii_hexdigit()))
+            .then_some(ExpressionKind::Int);
+    }
+
+    if !number.starts_with(|c: char| c.is_ascii_digit() || c == '.') {
+        return None;
+    }
+
+    if number.chars().all(|c| c.is_ascii_digit()) {
+        Some(ExpressionKind::Int)
+    } else if number.parse::<f64>().is_ok() && !number.ends_with('.') {
+        Some(ExpressionKind::Double)
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn names(names: &[&str]) -> Vec<Identifier> {
+        names
+            .iter()
+            .map(|name| Identifier {
+                name: name.to_string(),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn primitive_literals() {
+        assert_eq!(expression_kind(" null "), ExpressionKind::Null);
+        assert_eq!(expression_kind("false"), ExpressionKind::Bool);
+        assert_eq!(expression_kind("-1_000"), ExpressionKind::Int);
+        assert_eq!(expression_kind("0xFF"), ExpressionKind::Int);
+        assert_eq!(expression_kind("1.5e3"), ExpressionKind::Double);
+        assert_eq!(expression_kind("'a' \"b\""), ExpressionKind::String);
+    }
+
+    #[test]
+    fn collection_literals() {
+        assert_eq!(expression_kind("const [1, 2]"), ExpressionKind::List);
+        assert_eq!(expression_kind("<int>[]"), ExpressionKind::List);
+        assert_eq!(expression_kind("{}"), ExpressionKind::Map);
+        assert_eq!(expression_kind("{'a': [1]}"), ExpressionKind::Map);
+        assert_eq!(expression_kind("{'a', 'b'}"), ExpressionKind::Set);
+        assert_eq!(expression_kind("<int>{}"), ExpressionKind::Set);
+        assert_eq!(expression_kind("<int, int>{}"), ExpressionKind::Map);
+    }
+
+    #[test]
+    fn invocations() {
+        assert_eq!(
+            expression_kind("DateTime.now()"),
+            ExpressionKind::Invocation {
+                const_keyword: false,
+                new_keyword: false,
+                name: names(&["DateTime", "now"]),
+            }
+        );
+        assert_eq!(
+            expression_kind("const A<int>(b: [1])"),
+            ExpressionKind::Invocation {
+                const_keyword: true,
+                new_keyword: false,
+                name: names(&["A"]),
+            }
+        );
+        assert_eq!(
+            expression_kind("new a.B()"),
+            ExpressionKind::Invocation {
+                const_keyword: false,
+                new_keyword: true,
+                name: names(&["a", "B"]),
+            }
+        );
+    }
+
+    #[test]
+    fn names_and_other_expressions() {
+        assert_eq!(
+            expression_kind("A.b"),
+            ExpressionKind::Name(names(&["A", "b"]))
+        );
+        assert_eq!(expression_kind("1 + 2"), ExpressionKind::Other);
+        assert_eq!(expression_kind("[1] + [2]"), ExpressionKind::Other);
+        assert_eq!(expression_kind("a() + b()"), ExpressionKind::Other);
+    }
+}
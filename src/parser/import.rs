@@ -1,18 +1,69 @@
 use nom::{
-    bytes::complete::{tag, take_until},
-    IResult,
+    branch::alt,
+    bytes::complete::tag,
+    combinator::opt,
+    multi::{many0, separated_list1},
+    sequence::{delimited, preceded, tuple},
+    IResult, Parser,
 };
 
-use super::{keyword::import_keyword, literal::string_literal, whitespace::wsc};
+use super::{
+    identifier::{identifier, Identifier},
+    keyword::{as_keyword, deferred_keyword, hide_keyword, import_keyword, show_keyword},
+    literal::string_literal,
+    position::SourcePosition,
+    utils::comma_separated1,
+    whitespace::wsc,
+};
 
 #[derive(Debug, PartialEq)]
 pub struct ImportStatement {
     pub path: String,
+    /// Configurable URIs like `if (dart.library.io) 'io.dart'`
+    pub configurations: Vec<ImportConfiguration>,
+    pub deferred: bool,
+    pub prefix: Option<Identifier>,
+    pub combinators: Vec<Combinator>,
+    /// Position after the terminating semicolon
+    pub end_position: SourcePosition,
 }
 
 impl ImportStatement {
-    pub fn is_flutter_foundation_import(&self) -> bool {
-        self.path == "package:flutter/foundation.dart"
+    /// Returns true if the import makes `name` from the imported library
+    /// usable without a prefix.
+    pub fn makes_visible_without_prefix(&self, name: &str) -> bool {
+        if self.prefix.is_some() || self.deferred {
+            return false;
+        }
+
+        self.combinators
+            .iter()
+            .all(|combinator| combinator.allows(name))
+    }
+}
+
+/// Configurable import URI like `if (dart.library.io) 'io.dart'`
+#[derive(Debug, PartialEq)]
+pub struct ImportConfiguration {
+    /// Dotted name like `dart.library.io`
+    pub condition: String,
+    /// Value in condition like `'true'` in `if (a.b == 'true')`
+    pub value: Option<String>,
+    pub path: String,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Combinator {
+    Show(Vec<Identifier>),
+    Hide(Vec<Identifier>),
+}
+
+impl Combinator {
+    pub fn allows(&self, name: &str) -> bool {
+        match self {
+            Self::Show(names) => names.iter().any(|n| n.as_str() == name),
+            Self::Hide(names) => names.iter().all(|n| n.as_str() != name),
+        }
     }
 }
 
@@ -20,16 +71,106 @@ pub fn import_statement(input: &str) -> IResult<&str, ImportStatement> {
     let (input, _) = import_keyword(input)?;
     let (input, _) = wsc(input)?;
     let (input, path) = string_literal(input)?;
-    let (input, _) = take_until(";")(input)?;
+    let (input, _) = wsc(input)?;
+    let (input, configurations) = many0(import_configuration)(input)?;
+    let (input, _) = wsc(input)?;
+    let (input, deferred) = opt(deferred_keyword)(input)?;
+    let (input, _) = wsc(input)?;
+    let (input, prefix) = opt(preceded(tuple((as_keyword, wsc)), identifier))(input)?;
+    let (input, _) = wsc(input)?;
+    let (input, combinators) = many0(combinator)(input)?;
+    let (input, _) = wsc(input)?;
     let (input, _) = tag(";")(input)?;
+    let end_position = SourcePosition::from_remaining_input(input);
 
-    Ok((input, ImportStatement { path }))
+    Ok((
+        input,
+        ImportStatement {
+            path,
+            configurations,
+            deferred: deferred.is_some(),
+            prefix,
+            combinators,
+            end_position,
+        },
+    ))
+}
+
+pub fn import_configuration(input: &str) -> IResult<&str, ImportConfiguration> {
+    let (input, _) = tag("if")(input)?;
+    let (input, _) = wsc(input)?;
+    let (input, (condition, value)) = delimited(
+        tag("("),
+        tuple((
+            preceded(wsc, dotted_name),
+            opt(preceded(tuple((wsc, tag("=="), wsc)), string_literal)),
+        )),
+        preceded(wsc, tag(")")),
+    )(input)?;
+    let (input, _) = wsc(input)?;
+    let (input, path) = string_literal(input)?;
+    let (input, _) = wsc(input)?;
+
+    Ok((
+        input,
+        ImportConfiguration {
+            condition,
+            value,
+            path,
+        },
+    ))
+}
+
+/// Dotted name like `dart.library.io`
+pub fn dotted_name(input: &str) -> IResult<&str, String> {
+    separated_list1(tuple((wsc, tag("."), wsc)), identifier)
+        .map(|names| {
+            names
+                .iter()
+                .map(|name| name.as_str())
+                .collect::<Vec<&str>>()
+                .join(".")
+        })
+        .parse(input)
+}
+
+pub fn combinator(input: &str) -> IResult<&str, Combinator> {
+    let (input, combinator) = alt((
+        preceded(tuple((show_keyword, wsc)), |input| {
+            comma_separated1(identifier, input)
+        })
+        .map(Combinator::Show),
+        preceded(tuple((hide_keyword, wsc)), |input| {
+            comma_separated1(identifier, input)
+        })
+        .map(Combinator::Hide),
+    ))(input)?;
+    let (input, _) = wsc(input)?;
+
+    Ok((input, combinator))
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    fn id(name: &str) -> Identifier {
+        Identifier {
+            name: name.to_string(),
+        }
+    }
+
+    fn import(path: &str) -> ImportStatement {
+        ImportStatement {
+            path: path.to_string(),
+            configurations: vec![],
+            deferred: false,
+            prefix: None,
+            combinators: vec![],
+            end_position: SourcePosition::from_remaining_input(""),
+        }
+    }
+
     #[test]
     fn import_statement_other_than_import_statement() {
         assert!(import_statement("a").is_err());
@@ -42,9 +183,83 @@ mod tests {
             Ok((
                 "a",
                 ImportStatement {
-                    path: "hello".to_string()
+                    end_position: SourcePosition::from_remaining_input("a"),
+                    ..import("hello")
                 }
             ))
         );
     }
+
+    #[test]
+    fn import_statement_with_prefix_and_deferred() {
+        assert_eq!(
+            import_statement("import 'a' deferred as b;"),
+            Ok((
+                "",
+                ImportStatement {
+                    deferred: true,
+                    prefix: Some(id("b")),
+                    ..import("a")
+                }
+            ))
+        );
+    }
+
+    #[test]
+    fn import_statement_with_combinators() {
+        assert_eq!(
+            import_statement("import 'a' show A, B hide B;"),
+            Ok((
+                "",
+                ImportStatement {
+                    combinators: vec![
+                        Combinator::Show(vec![id("A"), id("B")]),
+                        Combinator::Hide(vec![id("B")]),
+                    ],
+                    ..import("a")
+                }
+            ))
+        );
+    }
+
+    #[test]
+    fn import_statement_with_configurations() {
+        assert_eq!(
+            import_statement(
+                "import 'a' if (dart.library.io) 'b' if (dart.library.js == 'true') 'c';"
+            ),
+            Ok((
+                "",
+                ImportStatement {
+                    configurations: vec![
+                        ImportConfiguration {
+                            condition: "dart.library.io".to_string(),
+                            value: None,
+                            path: "b".to_string(),
+                        },
+                        ImportConfiguration {
+                            condition: "dart.library.js".to_string(),
+                            value: Some("true".to_string()),
+                            path: "c".to_string(),
+                        },
+                    ],
+                    ..import("a")
+                }
+            ))
+        );
+    }
+
+    #[test]
+    fn makes_visible_without_prefix_handles_combinators() {
+        let (_, import) = import_statement("import 'a' show A, B hide B;").unwrap();
+        assert!(import.makes_visible_without_prefix("A"));
+        assert!(!import.makes_visible_without_prefix("B"));
+        assert!(!import.makes_visible_without_prefix("C"));
+    }
+
+    #[test]
+    fn makes_visible_without_prefix_prefixed_import() {
+        let (_, import) = import_statement("import 'a' as a;").unwrap();
+        assert!(!import.makes_visible_without_prefix("A"));
+    }
 }
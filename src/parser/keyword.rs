@@ -1,14 +1,27 @@
-use nom::{bytes::complete::tag, IResult};
+use nom::{branch::alt, bytes::complete::tag, combinator::peek, sequence::terminated, IResult};
 
 #[derive(Debug, PartialEq)]
 pub enum Keyword {
     Import,
+    Export,
     Class,
+    Enum,
     Factory,
     Required,
     With,
     Implements,
     Const,
+    Final,
+    Extends,
+    Deferred,
+    As,
+    Show,
+    Hide,
+    New,
+    Part,
+    Of,
+    Library,
+    Static,
 }
 
 // TODO: Keyword which ends in a comment does not work
@@ -17,10 +30,18 @@ pub fn import_keyword(input: &str) -> IResult<&str, Keyword> {
     tag("import ")(input).map(|(input, _)| (input, Keyword::Import))
 }
 
+pub fn export_keyword(input: &str) -> IResult<&str, Keyword> {
+    tag("export ")(input).map(|(input, _)| (input, Keyword::Export))
+}
+
 pub fn class_keyword(input: &str) -> IResult<&str, Keyword> {
     tag("class ")(input).map(|(input, _)| (input, Keyword::Class))
 }
 
+pub fn enum_keyword(input: &str) -> IResult<&str, Keyword> {
+    tag("enum ")(input).map(|(input, _)| (input, Keyword::Enum))
+}
+
 pub fn factory_keyword(input: &str) -> IResult<&str, Keyword> {
     tag("factory ")(input).map(|(input, _)| (input, Keyword::Factory))
 }
@@ -41,6 +62,53 @@ pub fn const_keyword(input: &str) -> IResult<&str, Keyword> {
     tag("const ")(input).map(|(input, _)| (input, Keyword::Const))
 }
 
+pub fn final_keyword(input: &str) -> IResult<&str, Keyword> {
+    tag("final ")(input).map(|(input, _)| (input, Keyword::Final))
+}
+
+pub fn extends_keyword(input: &str) -> IResult<&str, Keyword> {
+    tag("extends ")(input).map(|(input, _)| (input, Keyword::Extends))
+}
+
+pub fn deferred_keyword(input: &str) -> IResult<&str, Keyword> {
+    tag("deferred ")(input).map(|(input, _)| (input, Keyword::Deferred))
+}
+
+pub fn as_keyword(input: &str) -> IResult<&str, Keyword> {
+    tag("as ")(input).map(|(input, _)| (input, Keyword::As))
+}
+
+pub fn show_keyword(input: &str) -> IResult<&str, Keyword> {
+    tag("show ")(input).map(|(input, _)| (input, Keyword::Show))
+}
+
+pub fn new_keyword(input: &str) -> IResult<&str, Keyword> {
+    tag("new ")(input).map(|(input, _)| (input, Keyword::New))
+}
+
+pub fn hide_keyword(input: &str) -> IResult<&str, Keyword> {
+    tag("hide ")(input).map(|(input, _)| (input, Keyword::Hide))
+}
+
+pub fn part_keyword(input: &str) -> IResult<&str, Keyword> {
+    tag("part ")(input).map(|(input, _)| (input, Keyword::Part))
+}
+
+pub fn of_keyword(input: &str) -> IResult<&str, Keyword> {
+    tag("of ")(input).map(|(input, _)| (input, Keyword::Of))
+}
+
+/// Keyword `library`, which can be followed directly by `;`
+/// in an unnamed library directive.
+pub fn library_keyword(input: &str) -> IResult<&str, Keyword> {
+    terminated(tag("library"), peek(alt((tag(" "), tag(";")))))(input)
+        .map(|(input, _)| (input, Keyword::Library))
+}
+
+pub fn static_keyword(input: &str) -> IResult<&str, Keyword> {
+    tag("static ")(input).map(|(input, _)| (input, Keyword::Static))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -64,4 +132,10 @@ mod tests {
     fn class_keyword_parses_correctly() {
         assert_eq!(class_keyword("class a"), Ok(("a", Keyword::Class)));
     }
+
+    #[test]
+    fn library_keyword_followed_by_semicolon() {
+        assert_eq!(library_keyword("library;"), Ok((";", Keyword::Library)));
+        assert!(library_keyword("libraryName;").is_err());
+    }
 }
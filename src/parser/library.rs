@@ -0,0 +1,57 @@
+use nom::{bytes::complete::tag, combinator::opt, IResult};
+
+use super::{
+    import::dotted_name, keyword::library_keyword, position::SourcePosition, whitespace::wsc,
+};
+
+/// Directive like `library a.b;` or `library;`
+#[derive(Debug, PartialEq)]
+pub struct LibraryDirective {
+    pub name: Option<String>,
+    /// Position after the terminating semicolon
+    pub end_position: SourcePosition,
+}
+
+pub fn library_directive(input: &str) -> IResult<&str, LibraryDirective> {
+    let (input, _) = library_keyword(input)?;
+    let (input, _) = wsc(input)?;
+    let (input, name) = opt(dotted_name)(input)?;
+    let (input, _) = wsc(input)?;
+    let (input, _) = tag(";")(input)?;
+    let end_position = SourcePosition::from_remaining_input(input);
+
+    Ok((input, LibraryDirective { name, end_position }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn library_directive_with_name() {
+        assert_eq!(
+            library_directive("library a.b;"),
+            Ok((
+                "",
+                LibraryDirective {
+                    name: Some("a.b".to_string()),
+                    end_position: SourcePosition::from_remaining_input(""),
+                }
+            ))
+        );
+    }
+
+    #[test]
+    fn library_directive_without_name() {
+        assert_eq!(
+            library_directive("library;"),
+            Ok((
+                "",
+                LibraryDirective {
+                    name: None,
+                    end_position: SourcePosition::from_remaining_input(""),
+                }
+            ))
+        );
+    }
+}
@@ -0,0 +1,116 @@
+use nom::{branch::alt, bytes::complete::tag, IResult};
+
+use super::{
+    import::dotted_name,
+    keyword::{of_keyword, part_keyword},
+    literal::string_literal,
+    position::SourcePosition,
+    whitespace::wsc,
+};
+
+/// Directive like `part 'a.freezed.dart';`
+#[derive(Debug, PartialEq)]
+pub struct PartDirective {
+    pub path: String,
+    pub position: SourcePosition,
+    /// Position after the terminating semicolon
+    pub end_position: SourcePosition,
+}
+
+/// Directive like `part of 'a.dart';` or `part of a.b;`
+#[derive(Debug, PartialEq)]
+pub struct PartOfDirective {
+    /// URI or dotted name of the library
+    pub library: String,
+}
+
+pub fn part_directive(input: &str) -> IResult<&str, PartDirective> {
+    let position = SourcePosition::from_remaining_input(input);
+    let (input, _) = part_keyword(input)?;
+    let (input, _) = wsc(input)?;
+    let (input, path) = string_literal(input)?;
+    let (input, _) = wsc(input)?;
+    let (input, _) = tag(";")(input)?;
+    let end_position = SourcePosition::from_remaining_input(input);
+
+    Ok((
+        input,
+        PartDirective {
+            path,
+            position,
+            end_position,
+        },
+    ))
+}
+
+pub fn part_of_directive(input: &str) -> IResult<&str, PartOfDirective> {
+    let (input, _) = part_keyword(input)?;
+    let (input, _) = wsc(input)?;
+    let (input, _) = of_keyword(input)?;
+    let (input, _) = wsc(input)?;
+    let (input, library) = alt((string_literal, dotted_name))(input)?;
+    let (input, _) = wsc(input)?;
+    let (input, _) = tag(";")(input)?;
+
+    Ok((input, PartOfDirective { library }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn part_directive_parsed_correctly() {
+        let source = "part /* a */ 'a.freezed.dart' ;b";
+        assert_eq!(
+            part_directive(source),
+            Ok((
+                "b",
+                PartDirective {
+                    path: "a.freezed.dart".to_string(),
+                    position: SourcePosition::from_remaining_input(source),
+                    end_position: SourcePosition::from_remaining_input("b"),
+                }
+            ))
+        );
+    }
+
+    #[test]
+    fn part_directive_other_than_part_of() {
+        assert!(part_directive("part of 'a.dart';").is_err());
+    }
+
+    #[test]
+    fn part_of_directive_with_uri() {
+        assert_eq!(
+            part_of_directive("part of \"a.dart\";"),
+            Ok((
+                "",
+                PartOfDirective {
+                    library: "a.dart".to_string()
+                }
+            ))
+        );
+    }
+
+    #[test]
+    fn part_of_directive_with_library_name() {
+        assert_eq!(
+            part_of_directive("part of a . b;"),
+            Ok((
+                "",
+                PartOfDirective {
+                    library: "a.b".to_string()
+                }
+            ))
+        );
+    }
+
+    #[test]
+    fn part_directive_end_position() {
+        let source = "part 'a.dart'; class";
+        let (_, directive) = part_directive(source).unwrap();
+        assert_eq!(directive.position.offset(source), 0);
+        assert_eq!(directive.end_position.offset(source), 14);
+    }
+}
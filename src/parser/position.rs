@@ -0,0 +1,64 @@
+/// Position in the source file.
+///
+/// Parsers only see the remaining input, so the position is stored as
+/// the length of the input remaining after the position.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SourcePosition {
+    remaining_len: usize,
+}
+
+impl SourcePosition {
+    pub fn from_remaining_input(input: &str) -> Self {
+        Self {
+            remaining_len: input.len(),
+        }
+    }
+
+    /// Byte offset of the position in `source`, which is the
+    /// parsed source code.
+    pub fn offset(&self, source: &str) -> usize {
+        source.len().saturating_sub(self.remaining_len)
+    }
+
+    /// One-based line and column of the position in `source`,
+    /// which is the parsed source code.
+    pub fn line_and_column(&self, source: &str) -> (usize, usize) {
+        let before = source.get(..self.offset(source)).unwrap_or_default();
+        let line = before.matches('\n').count() + 1;
+        let line_start = before.rfind('\n').map(|i| i + 1).unwrap_or(0);
+        let column = before[line_start..].chars().count() + 1;
+        (line, column)
+    }
+}
+
+#[cfg(test)]
+impl SourcePosition {
+    /// Position of the first `pattern` in `source`
+    pub fn of(source: &str, pattern: &str) -> Self {
+        Self::from_remaining_input(&source[source.find(pattern).unwrap()..])
+    }
+
+    /// Position after the first `pattern` in `source`
+    pub fn after(source: &str, pattern: &str) -> Self {
+        Self::from_remaining_input(&source[source.find(pattern).unwrap() + pattern.len()..])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn line_and_column_first_line() {
+        let source = "abc";
+        let position = SourcePosition::from_remaining_input(&source[1..]);
+        assert_eq!(position.line_and_column(source), (1, 2));
+    }
+
+    #[test]
+    fn line_and_column_after_newlines() {
+        let source = "a\nb\n  äc";
+        let position = SourcePosition::from_remaining_input(&source[source.len() - 1..]);
+        assert_eq!(position.line_and_column(source), (3, 4));
+    }
+}
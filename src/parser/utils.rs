@@ -1,4 +1,5 @@
 use nom::{
+    branch::alt,
     bytes::complete::{tag, take},
     combinator::{not, opt},
     multi::many0,
@@ -6,28 +7,41 @@ use nom::{
     IResult,
 };
 
-use crate::parser::whitespace::wsc;
+use crate::parser::{
+    literal::string_literal_str,
+    whitespace::{some_whitespace, wsc, wsc_without_doc_comments},
+};
 
 pub fn comma_separated0<T>(
     parser: impl Fn(&str) -> IResult<&str, T>,
     input: &str,
 ) -> IResult<&str, Vec<T>> {
-    comma_separated(parser, false, input)
+    comma_separated(parser, false, wsc, input)
 }
 
 pub fn comma_separated1<T>(
     parser: impl Fn(&str) -> IResult<&str, T>,
     input: &str,
 ) -> IResult<&str, Vec<T>> {
-    comma_separated(parser, true, input)
+    comma_separated(parser, true, wsc, input)
+}
+
+/// Same as [comma_separated0], but doc comments before items are
+/// left for the item parser.
+pub fn documented_comma_separated0<T>(
+    parser: impl Fn(&str) -> IResult<&str, T>,
+    input: &str,
+) -> IResult<&str, Vec<T>> {
+    comma_separated(parser, false, wsc_without_doc_comments, input)
 }
 
 fn comma_separated<T>(
     parser: impl Fn(&str) -> IResult<&str, T>,
     require_first: bool,
+    skip: fn(&str) -> IResult<&str, &str>,
     input: &str,
 ) -> IResult<&str, Vec<T>> {
-    let (input, _) = wsc(input)?;
+    let (input, _) = skip(input)?;
     let (_, _) = not(tag(","))(input)?;
 
     let (input, first) = if require_first {
@@ -42,7 +56,7 @@ fn comma_separated<T>(
         return Ok((input, vec![]));
     };
 
-    let (input, more_parameters) = many0(preceded(tuple((wsc, tag(","), wsc)), parser))(input)?;
+    let (input, more_parameters) = many0(preceded(tuple((wsc, tag(","), skip)), parser))(input)?;
 
     parameters.extend(more_parameters);
 
@@ -60,7 +74,8 @@ pub fn item_parser<'a, T>(
     loop {
         let mut try_count = 0;
         loop {
-            let (input, _) = wsc(current_input)?;
+            // Doc comments are left for the item parser
+            let (input, _) = wsc_without_doc_comments(current_input)?;
             let (input, items) = many0(&mut item_parser)(input)?;
             try_count += 1;
             let items_is_empty = items.is_empty();
@@ -77,10 +92,48 @@ pub fn item_parser<'a, T>(
         if end_check(current_input) {
             break;
         } else {
-            let (input, _) = take(1usize)(current_input)?;
+            // Skip comment which was not consumed by the item parser
+            // or one character.
+            let (input, _) = alt((some_whitespace, take(1usize)))(current_input)?;
             current_input = input;
         }
     }
 
     Ok((current_input, all_items))
 }
+
+/// Split input from separators which are not inside brackets or
+/// string literals. Returns `None` if brackets are not balanced.
+pub fn split_top_level(input: &str, separator: char) -> Option<Vec<&str>> {
+    let mut parts = vec![];
+    let mut depth: usize = 0;
+    let mut part_start = 0;
+    let mut current_input = input;
+
+    while let Some(c) = current_input.chars().next() {
+        let consumed =
+            if let Ok((next, _)) = alt((some_whitespace, string_literal_str))(current_input) {
+                current_input.len() - next.len()
+            } else {
+                match c {
+                    '(' | '[' | '{' => depth += 1,
+                    ')' | ']' | '}' => depth = depth.checked_sub(1)?,
+                    c if c == separator && depth == 0 => {
+                        let position = input.len() - current_input.len();
+                        parts.push(&input[part_start..position]);
+                        part_start = position + 1;
+                    }
+                    _ => (),
+                }
+                c.len_utf8()
+            };
+        current_input = &current_input[consumed..];
+    }
+
+    if depth != 0 {
+        return None;
+    }
+
+    parts.push(&input[part_start..]);
+    Some(parts)
+}
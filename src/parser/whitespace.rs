@@ -2,15 +2,18 @@ use nom::{
     branch::alt,
     bytes::complete::{tag, take_until},
     character::complete::multispace1,
+    combinator::not,
     multi::many0,
-    sequence::delimited,
-    IResult,
+    sequence::{delimited, preceded},
+    IResult, Parser,
 };
 
 pub const LINE_COMMENT_START: &str = "//";
 pub const LINE_COMMENT_END: &str = "\n";
 pub const MULTI_LINE_COMMENT_START: &str = "/*";
 pub const MULTI_LINE_COMMENT_END: &str = "*/";
+pub const DOC_LINE_COMMENT_START: &str = "///";
+pub const DOC_MULTI_LINE_COMMENT_START: &str = "/**";
 
 /// Remove whitespace and comments from the input
 pub fn wsc(input: &str) -> IResult<&str, &str> {
@@ -18,6 +21,74 @@ pub fn wsc(input: &str) -> IResult<&str, &str> {
     Ok((input, ""))
 }
 
+/// Remove whitespace and comments from the input, but stop at the first
+/// doc comment.
+pub fn wsc_without_doc_comments(input: &str) -> IResult<&str, &str> {
+    let (input, _) = many0(preceded(
+        not(doc_comment),
+        alt((ws, line_comment, multi_line_comment)),
+    ))(input)?;
+    Ok((input, ""))
+}
+
+/// Remove whitespace and comments from the input and return
+/// lines of the doc comments.
+pub fn doc_comments0(input: &str) -> IResult<&str, Vec<String>> {
+    let (input, comments) = many0(alt((
+        doc_comment.map(Some),
+        alt((ws, line_comment, multi_line_comment)).map(|_| None),
+    )))(input)?;
+    Ok((input, comments.into_iter().flatten().flatten().collect()))
+}
+
+/// Doc comment like `/// a` or `/** a */`. Returns lines of the comment.
+fn doc_comment(input: &str) -> IResult<&str, Vec<String>> {
+    alt((doc_line_comment, doc_multi_line_comment))(input)
+}
+
+fn doc_line_comment(input: &str) -> IResult<&str, Vec<String>> {
+    // Comment like `//// a` is not a doc comment
+    let (input, _) = not(tag("////"))(input)?;
+    let (input, text) = delimited(
+        tag(DOC_LINE_COMMENT_START),
+        take_until(LINE_COMMENT_END),
+        tag(LINE_COMMENT_END),
+    )(input)?;
+    Ok((
+        input,
+        vec![format!("{}{}", DOC_LINE_COMMENT_START, text.trim_end())],
+    ))
+}
+
+fn doc_multi_line_comment(input: &str) -> IResult<&str, Vec<String>> {
+    // Comment `/**/` is not a doc comment
+    let (input, _) = not(tag("/**/"))(input)?;
+    let (input, text) = delimited(
+        tag(DOC_MULTI_LINE_COMMENT_START),
+        take_until(MULTI_LINE_COMMENT_END),
+        tag(MULTI_LINE_COMMENT_END),
+    )(input)?;
+
+    let comment = format!(
+        "{}{}{}",
+        DOC_MULTI_LINE_COMMENT_START, text, MULTI_LINE_COMMENT_END
+    );
+    let lines = comment
+        .lines()
+        .enumerate()
+        .map(|(i, line)| {
+            let line = line.trim();
+            // Align the stars of the following lines with the first line
+            if i > 0 && line.starts_with('*') {
+                format!(" {}", line)
+            } else {
+                line.to_string()
+            }
+        })
+        .collect();
+    Ok((input, lines))
+}
+
 /// Remove whitespace and comments partially.
 /// There still might be some whitespace left after running this parser.
 pub fn some_whitespace(input: &str) -> IResult<&str, &str> {
@@ -80,6 +151,30 @@ mod tests {
         assert_eq!(wsc("\n //a\n /*b*/\nc"), Ok(("c", "")));
     }
 
+    #[test]
+    fn wsc_without_doc_comments_stops_at_doc_comment() {
+        assert_eq!(
+            wsc_without_doc_comments(" //a\n /// b\n"),
+            Ok(("/// b\n", ""))
+        );
+    }
+
+    #[test]
+    fn doc_comments0_collects_lines() {
+        assert_eq!(
+            doc_comments0("/// a\n // b\n //// c\n /**\n   * d\n   */ e"),
+            Ok((
+                "e",
+                vec![
+                    "/// a".to_string(),
+                    "/**".to_string(),
+                    " * d".to_string(),
+                    " */".to_string(),
+                ]
+            ))
+        );
+    }
+
     #[test]
     fn wsc_no_whitespace_or_comments() {
         assert_eq!(wsc("a// comment\n"), Ok(("a// comment\n", "")));
@@ -0,0 +1,107 @@
+//! Encoding and line endings of Dart files
+
+use std::path::Path;
+
+use clap::ValueEnum;
+
+use crate::error::{Error, Location};
+use crate::parser::SourcePosition;
+
+const BYTE_ORDER_MARK: &str = "\u{feff}";
+
+/// Line ending of generated code files
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum)]
+pub enum LineEnding {
+    /// Same as the first line ending of the Dart file
+    #[default]
+    Auto,
+    /// Unix line ending `\n`
+    Lf,
+    /// Windows line ending `\r\n`
+    Crlf,
+}
+
+impl LineEnding {
+    /// Convert line endings of `code`, which is generated for the Dart
+    /// file `source`.
+    pub fn apply(self, source: &str, code: &str) -> String {
+        let crlf = match self {
+            Self::Auto => first_line_ends_with_crlf(source),
+            Self::Lf => false,
+            Self::Crlf => true,
+        };
+
+        // Doc comments and default values are copied from the source,
+        // so the code can already contain some `\r\n` line endings
+        let code = code.replace("\r\n", "\n");
+        if crlf {
+            code.replace('\n', "\r\n")
+        } else {
+            code
+        }
+    }
+}
+
+fn first_line_ends_with_crlf(source: &str) -> bool {
+    source
+        .find('\n')
+        .is_some_and(|i| source[..i].ends_with('\r'))
+}
+
+/// Decode UTF-8 contents of file `path`. The byte order mark is kept.
+pub(crate) fn decode(path: &Path, bytes: Vec<u8>) -> Result<String, Error> {
+    String::from_utf8(bytes).map_err(|e| {
+        let valid = String::from_utf8_lossy(&e.as_bytes()[..e.utf8_error().valid_up_to()]);
+        let (line, column) = SourcePosition::from_remaining_input("").line_and_column(&valid);
+        Error::InvalidUtf8 {
+            path: path.to_owned(),
+            location: Location { line, column },
+        }
+    })
+}
+
+/// Remove the byte order mark from the start of `source`. Returns true
+/// as the second value if it was removed.
+pub(crate) fn strip_byte_order_mark(source: &str) -> (&str, bool) {
+    match source.strip_prefix(BYTE_ORDER_MARK) {
+        Some(source) => (source, true),
+        None => (source, false),
+    }
+}
+
+/// Add the byte order mark to the start of `contents` if
+/// `has_byte_order_mark` is true
+pub(crate) fn with_byte_order_mark(contents: &str, has_byte_order_mark: bool) -> String {
+    if has_byte_order_mark {
+        format!("{}{}", BYTE_ORDER_MARK, contents)
+    } else {
+        contents.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn byte_order_mark_is_removed_and_restored() {
+        let source = decode(Path::new("a.dart"), b"\xef\xbb\xbfclass A {}".to_vec()).unwrap();
+        let (contents, has_byte_order_mark) = strip_byte_order_mark(&source);
+        assert_eq!((contents, has_byte_order_mark), ("class A {}", true));
+        assert_eq!(with_byte_order_mark(contents, has_byte_order_mark), source);
+        assert_eq!(strip_byte_order_mark("class A {}"), ("class A {}", false));
+    }
+
+    #[test]
+    fn invalid_utf8_is_located() {
+        let error = decode(Path::new("a.dart"), b"a\n  b\xff".to_vec()).unwrap_err();
+        assert_eq!(error.location(), Some(Location { line: 2, column: 4 }));
+    }
+
+    #[test]
+    fn line_ending_follows_first_line_of_source() {
+        assert_eq!(LineEnding::Auto.apply("a\r\nb\n", "c\nd\r\n"), "c\r\nd\r\n");
+        assert_eq!(LineEnding::Auto.apply("a\nb\r\n", "c\r\nd\n"), "c\nd\n");
+        assert_eq!(LineEnding::Crlf.apply("a\nb\n", "c\n"), "c\r\n");
+    }
+}
@@ -0,0 +1,201 @@
+//! Summary of handled files
+
+use std::collections::HashMap;
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+use crate::{event::Event, Error};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FileStatus {
+    Parsed,
+    Written,
+    Unchanged,
+    Skipped,
+    Failed,
+}
+
+impl FileStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Parsed => "parsed",
+            Self::Written => "written",
+            Self::Unchanged => "unchanged",
+            Self::Skipped => "skipped",
+            Self::Failed => "failed",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct FileSummary {
+    /// Path of the Dart file or the directory
+    pub path: PathBuf,
+    /// Number of generated classes
+    pub classes: usize,
+    pub status: FileStatus,
+}
+
+/// Status of every handled file and errors of the failed files
+#[derive(Debug, Default)]
+pub struct Summary {
+    pub files: Vec<FileSummary>,
+    pub errors: Vec<Error>,
+    indexes: HashMap<PathBuf, usize>,
+}
+
+impl Summary {
+    /// Returns true if no file failed
+    pub fn is_success(&self) -> bool {
+        self.errors.is_empty()
+    }
+
+    pub fn failed_files(&self) -> usize {
+        self.files
+            .iter()
+            .filter(|file| file.status == FileStatus::Failed)
+            .count()
+    }
+
+    /// Update the status of the file which the event is about
+    pub fn record(&mut self, event: &Event) {
+        match event {
+            Event::FileParsed { path } => {
+                self.file(path).status = FileStatus::Parsed;
+            }
+            Event::ClassGenerated { path, .. } => {
+                self.file(path).classes += 1;
+            }
+            Event::FileWritten { source_path, .. } => {
+                self.file(source_path).status = FileStatus::Written;
+            }
+            Event::FileUnchanged { source_path, .. } => {
+                self.file(source_path).status = FileStatus::Unchanged;
+            }
+            Event::FileFixed { path, .. } => {
+                self.file(path);
+            }
+            Event::FileSkipped { path, .. } => {
+                self.file(path).status = FileStatus::Skipped;
+            }
+            Event::FileFailed { path } => {
+                self.file(path).status = FileStatus::Failed;
+            }
+            // Errors are added when the run ends
+            Event::Diagnostic { .. } => (),
+        }
+    }
+
+    fn file(&mut self, path: &Path) -> &mut FileSummary {
+        let index = *self.indexes.entry(path.to_owned()).or_insert_with(|| {
+            self.files.push(FileSummary {
+                path: path.to_owned(),
+                classes: 0,
+                status: FileStatus::Parsed,
+            });
+            self.files.len() - 1
+        });
+
+        &mut self.files[index]
+    }
+}
+
+const PATH_HEADER: &str = "FILE";
+const CLASSES_HEADER: &str = "CLASSES";
+const STATUS_HEADER: &str = "STATUS";
+
+/// Table with one row per file and a line with totals
+impl fmt::Display for Summary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let paths: Vec<String> = self
+            .files
+            .iter()
+            .map(|file| file.path.display().to_string())
+            .collect();
+        let path_width = paths
+            .iter()
+            .map(|path| path.chars().count())
+            .chain([PATH_HEADER.len()])
+            .max()
+            .unwrap_or_default();
+        let classes_width = CLASSES_HEADER.len();
+
+        writeln!(
+            f,
+            "{:path_width$}  {:classes_width$}  {}",
+            PATH_HEADER, CLASSES_HEADER, STATUS_HEADER
+        )?;
+
+        for (file, path) in self.files.iter().zip(paths) {
+            writeln!(
+                f,
+                "{:path_width$}  {:>classes_width$}  {}",
+                path,
+                file.classes,
+                file.status.as_str()
+            )?;
+        }
+
+        let files = match self.files.len() {
+            1 => "1 file".to_string(),
+            count => format!("{} files", count),
+        };
+        write!(f, "{}, {} failed", files, self.failed_files())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn record_events_of_files() {
+        let mut summary = Summary::default();
+        summary.record(&Event::FileParsed { path: "a".into() });
+        summary.record(&Event::FileFailed { path: "b".into() });
+        summary.record(&Event::ClassGenerated {
+            path: "a".into(),
+            class_name: "A".to_string(),
+        });
+        summary.record(&Event::FileWritten {
+            path: "a.freezed.dart".into(),
+            source_path: "a".into(),
+        });
+
+        assert_eq!(
+            summary.files,
+            vec![
+                FileSummary {
+                    path: "a".into(),
+                    classes: 1,
+                    status: FileStatus::Written,
+                },
+                FileSummary {
+                    path: "b".into(),
+                    classes: 0,
+                    status: FileStatus::Failed,
+                },
+            ]
+        );
+        assert_eq!(summary.failed_files(), 1);
+    }
+
+    #[test]
+    fn table() {
+        let mut summary = Summary::default();
+        summary.record(&Event::FileUnchanged {
+            path: "lib/user.freezed.dart".into(),
+            source_path: "lib/user.dart".into(),
+        });
+        summary.record(&Event::FileFailed { path: "a".into() });
+
+        assert_eq!(
+            summary.to_string(),
+            "\
+FILE           CLASSES  STATUS
+lib/user.dart        0  unchanged
+a                    0  failed
+2 files, 1 failed"
+        );
+    }
+}
@@ -0,0 +1,65 @@
+//! Helpers for unit tests
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use crate::codegen::ValidatedClass;
+use crate::file_finder::DartFile;
+use crate::parser::TopLevelItems;
+
+static NEXT_ID: AtomicUsize = AtomicUsize::new(0);
+
+/// New directory in the temporary directory of the system. The
+/// directory is removed when this is dropped.
+pub struct TempDir {
+    path: PathBuf,
+}
+
+impl TempDir {
+    pub fn new() -> Self {
+        let id = NEXT_ID.fetch_add(1, Ordering::Relaxed);
+        let path = std::env::temp_dir().join(format!("icegen-test-{}-{}", process::id(), id));
+        fs::create_dir_all(&path).unwrap();
+        Self { path }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Write file `relative_path` and its parent directories. Returns
+    /// the path of the file.
+    pub fn write(&self, relative_path: &str, contents: &str) -> PathBuf {
+        let path = self.path.join(relative_path);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(&path, contents).unwrap();
+        path
+    }
+}
+
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.path);
+    }
+}
+
+/// Validate the class of Dart file `a.dart` with contents `source`,
+/// which must contain only the class
+pub fn validate_class(source: &str) -> anyhow::Result<ValidatedClass> {
+    let file = DartFile::parse(Path::new("a.dart"), source).unwrap();
+    match &file.parsed_file.items[..] {
+        [TopLevelItems::Class(class)] => ValidatedClass::validate(class, &file),
+        _ => panic!("class not parsed"),
+    }
+}
+
+/// Valid class `A` with factory constructor parameters `params`
+pub fn class_with_params(params: &str) -> ValidatedClass {
+    validate_class(&format!(
+        "@freezed class A with _$A {{ factory A({}) = _A; }}",
+        params
+    ))
+    .unwrap()
+}
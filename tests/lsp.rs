@@ -0,0 +1,327 @@
+//! Language server tests using a scripted client
+
+use std::fs;
+use std::path::PathBuf;
+
+use clap::Parser;
+use icegen::{config::ArgsConfig, lsp::serve};
+use serde_json::{json, Value};
+use tokio::io::{
+    split, AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader, DuplexStream, ReadHalf,
+    WriteHalf,
+};
+use tower_lsp::lsp_types::Url;
+
+const USER: &str = "import 'package:freezed_annotation/freezed_annotation.dart';
+
+@freezed
+class User {
+  const factory User({required String name}) = _User;
+}
+";
+
+const VALID_USER: &str = "import 'package:freezed_annotation/freezed_annotation.dart';
+
+part 'user.freezed.dart';
+
+@freezed
+class User with _$User {
+  factory User({required String name}) = _User;
+}
+";
+
+/// URI of a file which does not exist. Generated code is not
+/// written for it.
+const USER_URI: &str = "file:///lib/user.dart";
+
+struct TestClient {
+    reader: BufReader<ReadHalf<DuplexStream>>,
+    writer: WriteHalf<DuplexStream>,
+    next_id: i64,
+    /// Notifications received while waiting for responses
+    notifications: Vec<Value>,
+}
+
+impl TestClient {
+    /// Client connected to a new server which is not initialized yet
+    fn connect() -> Self {
+        let (client_stream, server_stream) = tokio::io::duplex(1 << 16);
+        let (server_reader, server_writer) = split(server_stream);
+        let config = ArgsConfig::parse_from(["icegen", "lsp"]);
+        tokio::spawn(serve(server_reader, server_writer, config));
+
+        let (reader, writer) = split(client_stream);
+        Self {
+            reader: BufReader::new(reader),
+            writer,
+            next_id: 1,
+            notifications: vec![],
+        }
+    }
+
+    async fn start() -> Self {
+        let mut client = Self::connect();
+        client
+            .request("initialize", json!({ "capabilities": {} }))
+            .await;
+        client.notify("initialized", json!({})).await;
+        client
+    }
+
+    async fn send(&mut self, message: Value) {
+        let content = message.to_string();
+        let framed = format!("Content-Length: {}\r\n\r\n{}", content.len(), content);
+        self.writer.write_all(framed.as_bytes()).await.unwrap();
+    }
+
+    async fn receive(&mut self) -> Value {
+        let mut content_length = 0;
+        loop {
+            let mut header = String::new();
+            self.reader.read_line(&mut header).await.unwrap();
+            let header = header.trim_end();
+            if header.is_empty() {
+                break;
+            }
+            if let Some(length) = header.strip_prefix("Content-Length: ") {
+                content_length = length.parse().unwrap();
+            }
+        }
+
+        let mut content = vec![0; content_length];
+        self.reader.read_exact(&mut content).await.unwrap();
+        serde_json::from_slice(&content).unwrap()
+    }
+
+    async fn request(&mut self, method: &str, params: Value) -> Value {
+        let id = self.next_id;
+        self.next_id += 1;
+        self.send(json!({ "jsonrpc": "2.0", "id": id, "method": method, "params": params }))
+            .await;
+
+        loop {
+            let message = self.receive().await;
+            if message["id"] == id && message.get("method").is_none() {
+                return message["result"].clone();
+            }
+            self.notifications.push(message);
+        }
+    }
+
+    async fn notify(&mut self, method: &str, params: Value) {
+        self.send(json!({ "jsonrpc": "2.0", "method": method, "params": params }))
+            .await;
+    }
+
+    async fn notification(&mut self, method: &str) -> Value {
+        if let Some(i) = self
+            .notifications
+            .iter()
+            .position(|message| message["method"] == method)
+        {
+            return self.notifications.remove(i)["params"].clone();
+        }
+
+        loop {
+            let message = self.receive().await;
+            if message["method"] == method {
+                return message["params"].clone();
+            }
+            self.notifications.push(message);
+        }
+    }
+
+    async fn open(&mut self, uri: &Url, text: &str) -> Value {
+        self.notify(
+            "textDocument/didOpen",
+            json!({
+                "textDocument": { "uri": uri, "languageId": "dart", "version": 1, "text": text },
+            }),
+        )
+        .await;
+        self.notification("textDocument/publishDiagnostics").await
+    }
+}
+
+fn temp_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("icegen-lsp-{}-{}", std::process::id(), name));
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+fn user_uri(dir: &std::path::Path) -> Url {
+    Url::from_file_path(dir.join("user.dart")).unwrap()
+}
+
+fn codes(diagnostics: &Value) -> Vec<&str> {
+    diagnostics["diagnostics"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|diagnostic| diagnostic["code"].as_str().unwrap())
+        .collect()
+}
+
+#[tokio::test]
+async fn initialize_reports_capabilities() {
+    let mut client = TestClient::connect();
+    let result = client
+        .request("initialize", json!({ "capabilities": {} }))
+        .await;
+
+    let capabilities = &result["capabilities"];
+    assert_eq!(capabilities["textDocumentSync"]["change"], 1);
+    assert_eq!(
+        capabilities["codeActionProvider"]["codeActionKinds"],
+        json!(["quickfix", "refactor", "source"])
+    );
+    assert_eq!(
+        capabilities["executeCommandProvider"]["commands"],
+        json!(["icegen.regenerate"])
+    );
+    assert_eq!(result["serverInfo"]["name"], "icegen");
+    assert_eq!(
+        result["serverInfo"]["version"],
+        env!("ICEGEN_BUILD_VERSION")
+    );
+}
+
+#[tokio::test]
+async fn diagnostics_for_missing_part_directive_and_mixin() {
+    let mut client = TestClient::start().await;
+    let uri = Url::parse(USER_URI).unwrap();
+
+    let diagnostics = client.open(&uri, USER).await;
+
+    assert_eq!(codes(&diagnostics), vec!["generate", "part-directive"]);
+    assert_eq!(
+        diagnostics["diagnostics"][0]["range"],
+        json!({ "start": { "line": 3, "character": 6 }, "end": { "line": 3, "character": 12 } })
+    );
+    assert_eq!(
+        diagnostics["diagnostics"][0]["message"],
+        "Class User does not mix in the generated mixin _$User. Add `with _$User` after the class name."
+    );
+}
+
+#[tokio::test]
+async fn diagnostics_are_updated_on_change() {
+    let mut client = TestClient::start().await;
+    let uri = Url::parse(USER_URI).unwrap();
+    client.open(&uri, VALID_USER).await;
+
+    let text = VALID_USER.replace("required String name", "@Default(1) String name");
+    client
+        .notify(
+            "textDocument/didChange",
+            json!({
+                "textDocument": { "uri": uri, "version": 2 },
+                "contentChanges": [{ "text": text }],
+            }),
+        )
+        .await;
+    let diagnostics = client.notification("textDocument/publishDiagnostics").await;
+
+    assert_eq!(codes(&diagnostics), vec!["generate"]);
+    assert_eq!(
+        diagnostics["diagnostics"][0]["range"]["start"],
+        json!({ "line": 6, "character": 16 })
+    );
+}
+
+#[tokio::test]
+async fn code_actions_fix_class() {
+    let mut client = TestClient::start().await;
+    let uri = Url::parse(USER_URI).unwrap();
+    let diagnostics = client.open(&uri, USER).await;
+
+    let actions = client
+        .request(
+            "textDocument/codeAction",
+            json!({
+                "textDocument": { "uri": uri },
+                "range": { "start": { "line": 3, "character": 7 }, "end": { "line": 3, "character": 7 } },
+                "context": { "diagnostics": diagnostics["diagnostics"] },
+            }),
+        )
+        .await;
+
+    let titles: Vec<&str> = actions
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|action| action["title"].as_str().unwrap())
+        .collect();
+    assert_eq!(
+        titles,
+        vec![
+            "Add `part 'user.freezed.dart';`",
+            "Add `with _$User`",
+            "Add private constructor `const User._();`",
+            "Regenerate `user.freezed.dart`",
+        ]
+    );
+
+    let uri_key = uri.to_string();
+    let edit = |i: usize| actions[i]["edit"]["changes"][&uri_key][0].clone();
+    assert_eq!(
+        edit(0),
+        json!({
+            "range": { "start": { "line": 0, "character": 60 }, "end": { "line": 0, "character": 60 } },
+            "newText": "\n\npart 'user.freezed.dart';",
+        })
+    );
+    assert_eq!(
+        edit(1),
+        json!({
+            "range": { "start": { "line": 3, "character": 10 }, "end": { "line": 3, "character": 10 } },
+            "newText": " with _$User",
+        })
+    );
+    assert_eq!(edit(2)["newText"], json!("\n  const User._();\n"));
+    assert_eq!(actions[0]["diagnostics"][0]["code"], "part-directive");
+    assert_eq!(actions[1]["diagnostics"][0]["code"], "generate");
+    assert_eq!(actions[3]["command"]["command"], "icegen.regenerate");
+}
+
+#[tokio::test]
+async fn save_updates_generated_file() {
+    let mut client = TestClient::start().await;
+    let dir = temp_dir("save");
+    let uri = user_uri(&dir);
+    let generated_path = dir.join("user.freezed.dart");
+    let _ = fs::remove_file(&generated_path);
+
+    client.open(&uri, VALID_USER).await;
+    client
+        .notify(
+            "textDocument/didSave",
+            json!({ "textDocument": { "uri": uri }, "text": VALID_USER }),
+        )
+        .await;
+    client.notification("window/logMessage").await;
+
+    let generated = fs::read_to_string(&generated_path).unwrap();
+    assert!(generated.contains("part of 'user.dart';"));
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[tokio::test]
+async fn regenerate_command_updates_generated_file() {
+    let mut client = TestClient::start().await;
+    let dir = temp_dir("regenerate");
+    let uri = user_uri(&dir);
+    let generated_path = dir.join("user.freezed.dart");
+    let _ = fs::remove_file(&generated_path);
+
+    client.open(&uri, VALID_USER).await;
+    client
+        .request(
+            "workspace/executeCommand",
+            json!({ "command": "icegen.regenerate", "arguments": [uri] }),
+        )
+        .await;
+
+    assert!(generated_path.exists());
+    fs::remove_dir_all(dir).unwrap();
+}